@@ -91,3 +91,12 @@ impl<K: CstKind> CstNode<K> {
         }
     }
 }
+
+/// Converts a byte offset into a 1-based line and column (in characters).
+#[must_use]
+pub fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
@@ -0,0 +1,96 @@
+use crate::diagnostic::Diagnostic;
+use crate::{SourceKind, collect_files, to_file_url};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use type_ml::{LayoutModel, LayoutWatcher, RmlParser};
+use type_ml_definitions::AnalysisWorkspace;
+
+pub fn run(paths: &[PathBuf]) -> ExitCode {
+    let files = match collect_files(paths) {
+        Ok(files) => files,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failed = 0;
+    for file in &files {
+        match check_file(file) {
+            Ok(warnings) => warnings
+                .iter()
+                .for_each(|warning| Diagnostic::warning(file, warning).emit()),
+            Err(message) => {
+                Diagnostic::new(file, message).emit();
+                failed += 1;
+            }
+        }
+    }
+
+    println!("Checked {} file(s): {failed} with errors", files.len());
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Runs a full check once, then revalidates the layouts affected by each change until interrupted.
+pub fn watch(paths: &[PathBuf]) -> ExitCode {
+    run(paths);
+
+    let mut watcher = match LayoutWatcher::new(paths) {
+        Ok(watcher) => watcher,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("Watching for changes...");
+    loop {
+        let reports = match watcher.wait() {
+            Ok(reports) => reports,
+            Err(error) => {
+                eprintln!("error: {error}");
+                return ExitCode::FAILURE;
+            }
+        };
+
+        let mut failed = 0;
+        for report in &reports {
+            if let Err(error) = &report.result {
+                Diagnostic::new(&report.path, error).emit();
+                failed += 1;
+            }
+        }
+        println!("Revalidated {} layout(s): {failed} with errors", reports.len());
+    }
+}
+
+/// Validates a single file and returns its warnings.
+pub fn check_file(path: &Path) -> Result<Vec<String>, String> {
+    let mut warnings = Vec::new();
+    match SourceKind::from_path(path) {
+        Some(SourceKind::Layout) => {
+            let url = to_file_url(path)?;
+            let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+            let ast = RmlParser::build_ast(&content).map_err(|e| e.to_string())?;
+            let path = url.to_file_path().expect("Unreachable!");
+            warnings.extend(
+                ast.unused_impls()
+                    .iter()
+                    .map(|i| format!("Unused impl ${}", i.identifier)),
+            );
+            LayoutModel::validate(ast, &path.to_string_lossy()).map_err(|e| e.to_string())?;
+        }
+        Some(SourceKind::Schema) => {
+            AnalysisWorkspace::new(to_file_url(path)?)
+                .run()
+                .map_err(|e| e.to_string())?;
+        }
+        None => return Err("Unsupported file extension".to_string()),
+    }
+
+    Ok(warnings)
+}
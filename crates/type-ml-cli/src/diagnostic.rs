@@ -0,0 +1,71 @@
+use std::fmt::{Display, Formatter};
+use std::io::IsTerminal;
+use std::path::Path;
+
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const RESET: &str = "\x1b[0m";
+
+/// An error or warning reported against a single source file.
+pub struct Diagnostic<'a> {
+    path: &'a Path,
+    message: String,
+    warning: bool,
+    colored: bool,
+}
+
+impl<'a> Diagnostic<'a> {
+    pub fn new(path: &'a Path, message: impl Display) -> Self {
+        Self {
+            path,
+            message: message.to_string(),
+            warning: false,
+            colored: std::io::stderr().is_terminal(),
+        }
+    }
+
+    pub fn warning(path: &'a Path, message: impl Display) -> Self {
+        Self {
+            warning: true,
+            ..Self::new(path, message)
+        }
+    }
+
+    pub fn emit(&self) {
+        eprint!("{self}");
+    }
+}
+
+impl Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (red, yellow, blue, reset) = if self.colored {
+            (RED, YELLOW, BLUE, RESET)
+        } else {
+            ("", "", "", "")
+        };
+
+        // Pest errors start with their own ` --> line:col` header followed by a source snippet,
+        // so the file path is merged into that header instead of printing a second one.
+        let mut lines = self.message.lines();
+        let first = lines.next().unwrap_or_default();
+        if let Some(position) = first.trim_start().strip_prefix("--> ") {
+            writeln!(f, "{red}error{reset}: syntax error")?;
+            writeln!(f, "  {blue}-->{reset} {}:{position}", self.path.display())?;
+            for line in lines {
+                writeln!(f, "  {line}")?;
+            }
+        } else if self.warning {
+            writeln!(f, "{yellow}warning{reset}: {first}")?;
+            writeln!(f, "  {blue}-->{reset} {}", self.path.display())?;
+        } else {
+            writeln!(f, "{red}error{reset}: {first}")?;
+            for line in lines {
+                writeln!(f, "       {line}")?;
+            }
+            writeln!(f, "  {blue}-->{reset} {}", self.path.display())?;
+        }
+
+        writeln!(f)
+    }
+}
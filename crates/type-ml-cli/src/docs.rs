@@ -0,0 +1,747 @@
+use crate::diagnostic::Diagnostic;
+use crate::{DocFormat, SourceKind, to_file_url};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::process::ExitCode;
+use type_ml_definitions::{
+    AnalysisWorkspace, BaseType, Constraints, GroupSymbol, SchemaModel, Symbol, SymbolKind, SymbolRef,
+};
+
+pub fn run(path: &Path, format: DocFormat, output: &Path) -> ExitCode {
+    if SourceKind::from_path(path) != Some(SourceKind::Schema) {
+        Diagnostic::new(path, "Expected a .tmd file").emit();
+        return ExitCode::FAILURE;
+    }
+
+    let model = match to_file_url(path).and_then(|url| AnalysisWorkspace::new(url).run().map_err(|e| e.to_string())) {
+        Ok(model) => model,
+        Err(message) => {
+            Diagnostic::new(path, message).emit();
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let pages = (0..model.namespaces().len())
+        .map(|namespace| Page::new(&model, namespace))
+        .collect::<Vec<_>>();
+    let written = std::fs::create_dir_all(output).and_then(|()| {
+        pages.iter().try_for_each(|page| {
+            let printer = Printer {
+                page,
+                pages: &pages,
+                format,
+            };
+            std::fs::write(output.join(page.file_name(format)), printer.to_string())
+        })
+    });
+
+    if let Err(error) = written {
+        eprintln!("error: {}: {error}", output.display());
+        return ExitCode::FAILURE;
+    }
+
+    println!("Wrote {} page(s) to {}", pages.len(), output.display());
+    ExitCode::SUCCESS
+}
+
+/// A piece of inline text. Links point to the section of a symbol, possibly on the page of another namespace.
+enum Span {
+    Plain(String),
+    Code(String),
+    Link {
+        text: String,
+        page: Option<String>,
+        anchor: String,
+    },
+}
+
+/// Format independent inline text.
+#[derive(Default)]
+struct Text(Vec<Span>);
+
+impl Text {
+    fn plain(value: impl Into<String>) -> Self {
+        Self(vec![Span::Plain(value.into())])
+    }
+
+    fn code(value: impl Into<String>) -> Self {
+        Self(vec![Span::Code(value.into())])
+    }
+
+    /// Adds a span. Adjacent code spans are merged, Markdown can't put them side by side.
+    fn push(&mut self, span: Span) {
+        match (self.0.last_mut(), span) {
+            (Some(Span::Code(last)), Span::Code(value)) => last.push_str(&value),
+            (_, span) => self.0.push(span),
+        }
+    }
+
+    fn push_plain(&mut self, value: impl Into<String>) {
+        self.push(Span::Plain(value.into()));
+    }
+
+    fn push_code(&mut self, value: impl Into<String>) {
+        self.push(Span::Code(value.into()));
+    }
+
+    fn append(&mut self, other: Text) {
+        other.0.into_iter().for_each(|span| self.push(span));
+    }
+
+    /// Concatenates the texts, separated by `separator`.
+    fn join(texts: impl IntoIterator<Item = Text>, separator: &str) -> Self {
+        let mut joined = Text::default();
+        for (i, text) in texts.into_iter().enumerate() {
+            if i > 0 {
+                joined.push_plain(separator);
+            }
+            joined.append(text);
+        }
+        joined
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// The columns of the field table of a struct, element or expression.
+const FIELD_COLUMNS: [&str; 5] = ["Field", "Type", "Default", "Notes", "Description"];
+/// The columns of the variant table of an enum.
+const VARIANT_COLUMNS: [&str; 4] = ["Variant", "Payload", "Pattern", "Description"];
+
+/// The documentation of a single symbol.
+struct Entry {
+    anchor: String,
+    title: String,
+    doc: Option<String>,
+    facts: Vec<(&'static str, Text)>,
+    columns: &'static [&'static str],
+    rows: Vec<Vec<Text>>,
+}
+
+impl Entry {
+    fn new(keyword: &str, symbol: &SymbolKind) -> Self {
+        Self {
+            anchor: symbol.identifier().to_string(),
+            title: format!("{keyword} {}", symbol.identifier()),
+            doc: symbol.doc().map(str::to_string),
+            facts: vec![],
+            columns: &[],
+            rows: vec![],
+        }
+    }
+
+    fn fact(&mut self, name: &'static str, text: Text) {
+        if !text.is_empty() {
+            self.facts.push((name, text));
+        }
+    }
+}
+
+/// The sections of a page, in the order they are written.
+const SECTIONS: [&str; 8] = [
+    "Elements",
+    "Groups",
+    "Expressions",
+    "Structs",
+    "Enums",
+    "Generics",
+    "Type aliases",
+    "Constants",
+];
+
+/// The documentation of a namespace.
+struct Page {
+    namespace: String,
+    sections: Vec<(&'static str, Vec<Entry>)>,
+}
+
+impl Page {
+    fn new(model: &SchemaModel, namespace: usize) -> Self {
+        let context = Context { model, namespace };
+        let mut sections = SECTIONS.map(|title| (title, vec![]));
+        for (id, symbol) in model.modules()[namespace].iter().enumerate() {
+            let symbol_ref = SymbolRef { namespace, id };
+            if let Some((section, entry)) = context.entry(symbol_ref, symbol) {
+                sections[section].1.push(entry);
+            }
+        }
+
+        let mut sections = sections
+            .into_iter()
+            .filter(|(_, entries)| !entries.is_empty())
+            .collect::<Vec<_>>();
+        sections
+            .iter_mut()
+            .for_each(|(_, entries)| entries.sort_by(|a, b| a.anchor.cmp(&b.anchor)));
+        Self {
+            namespace: model.namespaces()[namespace].clone(),
+            sections,
+        }
+    }
+
+    /// The global namespace is the index of the site.
+    fn stem(namespace: &str) -> &str {
+        if namespace.is_empty() { "index" } else { namespace }
+    }
+
+    fn file_name(&self, format: DocFormat) -> String {
+        match format {
+            DocFormat::Markdown => format!("{}.md", Self::stem(&self.namespace)),
+            DocFormat::Html => format!("{}.html", Self::stem(&self.namespace)),
+        }
+    }
+
+    fn title(&self) -> String {
+        if self.namespace.is_empty() {
+            "Global namespace".to_string()
+        } else {
+            format!("Namespace {}", self.namespace)
+        }
+    }
+}
+
+/// Collects the documentation of the symbols of one namespace.
+struct Context<'a> {
+    model: &'a SchemaModel,
+    namespace: usize,
+}
+
+impl Context<'_> {
+    fn kind(&self, symbol_ref: SymbolRef) -> &SymbolKind {
+        self.model.get_type_by_ref(symbol_ref).unwrap().expect("Unreachable!")
+    }
+
+    /// Whether the symbol has a section of its own. Primitives, built-in generics and the instances of generics
+    /// are only mentioned, as is the `Main` group the model adds above `Root`.
+    fn is_documented(&self, symbol_ref: SymbolRef) -> bool {
+        let symbol = self.kind(symbol_ref);
+        match symbol {
+            SymbolKind::Struct(_) | SymbolKind::Enum(_) => !symbol.identifier().contains('<'),
+            SymbolKind::Generic(_) => !matches!(symbol.identifier(), "Option" | "Array" | "Map"),
+            SymbolKind::Group(_) => symbol_ref != self.model.get_main_group_ref(),
+            SymbolKind::Element(_) | SymbolKind::Expression(_) | SymbolKind::Alias(_) | SymbolKind::Constant(_) => true,
+            _ => false,
+        }
+    }
+
+    fn name(&self, symbol_ref: SymbolRef) -> String {
+        let identifier = self.kind(symbol_ref).identifier();
+        if symbol_ref.namespace == self.namespace || symbol_ref.namespace == 0 {
+            identifier.to_string()
+        } else {
+            format!("{}::{identifier}", self.model.namespaces()[symbol_ref.namespace])
+        }
+    }
+
+    /// The name of the symbol, linked to its section when it has one.
+    fn link(&self, symbol_ref: SymbolRef) -> Text {
+        if !self.is_documented(symbol_ref) {
+            return Text::code(self.name(symbol_ref));
+        }
+
+        let page = (symbol_ref.namespace != self.namespace)
+            .then(|| Page::stem(&self.model.namespaces()[symbol_ref.namespace]).to_string());
+        Text(vec![Span::Link {
+            text: self.name(symbol_ref),
+            page,
+            anchor: self.kind(symbol_ref).identifier().to_string(),
+        }])
+    }
+
+    fn links(&self, refs: impl IntoIterator<Item = SymbolRef>) -> Text {
+        Text::join(refs.into_iter().map(|symbol_ref| self.link(symbol_ref)), ", ")
+    }
+
+    /// The type of a field, with every documented type in it linked.
+    fn type_text(&self, symbol_ref: SymbolRef) -> Text {
+        let wrapped = |name: &str, inner: &[SymbolRef]| {
+            let mut text = Text::code(format!("{name}<"));
+            for (i, inner) in inner.iter().enumerate() {
+                if i > 0 {
+                    text.push_code(", ");
+                }
+                text.append(self.type_text(*inner));
+            }
+            text.push_code(">");
+            text
+        };
+
+        match self.kind(symbol_ref) {
+            SymbolKind::Option(option) => wrapped("Option", &[option.inner()]),
+            SymbolKind::Array(array) => wrapped("Array", &[array.inner()]),
+            SymbolKind::Map(map) => wrapped("Map", &[map.key(), map.value()]),
+            _ => self.link(symbol_ref),
+        }
+    }
+
+    /// The values of the enum a field holds, also through `Option` and `Array`.
+    fn allowed_values(&self, symbol_ref: SymbolRef) -> Option<Text> {
+        match self.kind(symbol_ref) {
+            SymbolKind::Option(option) => self.allowed_values(option.inner()),
+            SymbolKind::Array(array) => self.allowed_values(array.inner()),
+            SymbolKind::Alias(alias) => self.allowed_values(alias.target()),
+            SymbolKind::Enum(symbol) => {
+                let values = symbol.variants.iter().map(|variant| match &variant.ty {
+                    Some(ty) => Text::code(format!("{}({})", variant.identifier, self.name(ty.as_concrete()))),
+                    None => Text::code(&variant.identifier),
+                });
+                let mut text = Text::plain("one of ");
+                text.append(Text::join(values, ", "));
+                Some(text)
+            }
+            _ => None,
+        }
+    }
+
+    fn field_row(
+        &self,
+        field: (&str, SymbolRef, Option<&str>, Option<&str>),
+        requirement: Option<&str>,
+        constraints: &Constraints,
+    ) -> Vec<Text> {
+        let (identifier, ty, default, doc) = field;
+        let mut notes = vec![];
+        if let Some(requirement) = requirement {
+            notes.push(Text::plain(requirement));
+        }
+        notes.extend(constraint_texts(constraints));
+        notes.extend(self.allowed_values(ty));
+
+        vec![
+            Text::code(identifier),
+            self.type_text(ty),
+            default.map(Text::code).unwrap_or_default(),
+            Text::join(notes, "; "),
+            doc.map(|doc| Text::plain(doc.replace('\n', " "))).unwrap_or_default(),
+        ]
+    }
+
+    /// The child entries of a group: `unique` entries, counts and `one_of(...)` alternatives.
+    fn children(&self, group: &GroupSymbol) -> Text {
+        let mut children = vec![];
+        let mut printed_choices = vec![];
+        for entry in group.groups() {
+            if let Some(choice) = entry.choice() {
+                if !printed_choices.contains(&choice) {
+                    printed_choices.push(choice);
+                    let alternatives = group.groups().iter().filter(|g| g.choice() == Some(choice));
+                    let mut text = Text::plain("one of ");
+                    text.append(Text::join(alternatives.map(|g| self.link(g.symbol())), " | "));
+                    children.push(text);
+                }
+                continue;
+            }
+
+            let mut text = self.link(entry.symbol());
+            let count = entry.count().map_or_else(|| "0+".to_string(), |c| c.to_string());
+            if entry.unique() {
+                text.push_plain(format!(" (unique, {count})"));
+            } else {
+                text.push_plain(format!(" ({count})"));
+            }
+            children.push(text);
+        }
+
+        let mut text = Text::join(children, ", ");
+        if !text.is_empty() && group.ordered() {
+            text.push_plain(", in this order");
+        }
+        text
+    }
+
+    /// Every symbol of the model that satisfies `filter`.
+    fn find(&self, filter: impl Fn(&SymbolKind) -> bool) -> Vec<SymbolRef> {
+        let mut refs = vec![];
+        for (namespace, symbols) in self.model.modules().iter().enumerate() {
+            for (id, symbol) in symbols.iter().enumerate() {
+                if filter(symbol) {
+                    refs.push(SymbolRef { namespace, id });
+                }
+            }
+        }
+        refs
+    }
+
+    fn entry(&self, symbol_ref: SymbolRef, symbol: &SymbolKind) -> Option<(usize, Entry)> {
+        if !self.is_documented(symbol_ref) {
+            return None;
+        }
+
+        let section = |title: &str| SECTIONS.iter().position(|s| *s == title).expect("Unreachable!");
+        let result = match symbol {
+            SymbolKind::Element(element) => {
+                let mut entry = Entry::new("element", symbol);
+                entry.fact("Attributes", metadata(element.metadata()));
+                entry.fact("Group", self.link(element.group()));
+                if let SymbolKind::Group(group) = self.kind(element.group()) {
+                    entry.fact("Children", self.children(group));
+                }
+                entry.columns = &FIELD_COLUMNS;
+                entry.rows = element
+                    .fields()
+                    .iter()
+                    .map(|f| {
+                        let field = (f.identifier(), f.ty(), f.default(), f.doc());
+                        self.field_row(field, f.is_required().then_some("required"), f.constraints())
+                    })
+                    .collect();
+                (section("Elements"), entry)
+            }
+            SymbolKind::Group(group) => {
+                let mut entry = Entry::new("group", symbol);
+                entry.fact("Attributes", metadata(group.metadata()));
+                if group.extend() {
+                    entry.fact("Extendable", Text::plain("other namespaces may add members"));
+                }
+                let members = self.find(|s| match s {
+                    SymbolKind::Element(element) => element.group() == symbol_ref,
+                    SymbolKind::Expression(expression) => expression.groups().contains(&symbol_ref),
+                    _ => false,
+                });
+                entry.fact("Members", self.links(members));
+                entry.fact("Children", self.children(group));
+                let parents = self.find(|s| match s {
+                    SymbolKind::Group(parent) => parent.groups().iter().any(|g| g.symbol() == symbol_ref),
+                    _ => false,
+                });
+                let parents = parents.into_iter().filter(|parent| self.is_documented(*parent));
+                entry.fact("Allowed in", self.links(parents));
+                (section("Groups"), entry)
+            }
+            SymbolKind::Expression(expression) => {
+                let mut entry = Entry::new("expression", symbol);
+                entry.fact("Attributes", metadata(expression.metadata()));
+                let fields = expression.fields().iter().map(|f| {
+                    let field = format!("{}: {}", f.identifier(), self.name(f.ty()));
+                    if f.is_optional() { format!("[{field}]") } else { field }
+                });
+                let fields = fields.collect::<Vec<_>>().join(", ");
+                let signature = format!("{{{} {fields}}}", self.model.type_path(symbol_ref));
+                entry.fact("Signature", Text::code(signature));
+                entry.fact("Groups", self.links(expression.groups().iter().copied()));
+                entry.fact("Restricted to", self.links(expression.restrict().iter().copied()));
+                entry.columns = &FIELD_COLUMNS;
+                entry.rows = expression
+                    .fields()
+                    .iter()
+                    .map(|f| {
+                        let field = (f.identifier(), f.ty(), f.default(), f.doc());
+                        self.field_row(field, f.is_optional().then_some("optional"), f.constraints())
+                    })
+                    .collect();
+                (section("Expressions"), entry)
+            }
+            SymbolKind::Struct(structure) => {
+                let mut entry = Entry::new("struct", symbol);
+                entry.fact("Attributes", metadata(&structure.metadata));
+                entry.columns = &FIELD_COLUMNS;
+                entry.rows = structure
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        let field = (f.identifier(), f.ty(), f.default(), f.doc());
+                        let required = f.is_required(self.model).then_some("required");
+                        self.field_row(field, required, f.constraints())
+                    })
+                    .collect();
+                (section("Structs"), entry)
+            }
+            SymbolKind::Enum(enumeration) => {
+                let mut entry = Entry::new("enum", symbol);
+                entry.fact("Attributes", metadata(&enumeration.metadata));
+                entry.columns = &VARIANT_COLUMNS;
+                entry.rows = enumeration
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        let pattern = variant.pattern.as_ref().map(|pattern| {
+                            let mut text = Text::code(pattern.source());
+                            if !pattern.is_anchored() {
+                                text.push_plain(" (unanchored)");
+                            }
+                            text
+                        });
+                        vec![
+                            Text::code(&variant.identifier),
+                            variant
+                                .ty
+                                .as_ref()
+                                .map(|ty| self.type_text(ty.as_concrete()))
+                                .unwrap_or_default(),
+                            pattern.unwrap_or_default(),
+                            variant
+                                .doc
+                                .as_deref()
+                                .map(|doc| Text::plain(doc.replace('\n', " ")))
+                                .unwrap_or_default(),
+                        ]
+                    })
+                    .collect();
+                (section("Enums"), entry)
+            }
+            SymbolKind::Generic(generic) => {
+                let mut entry = Entry::new("generic", symbol);
+                entry.title = format!("generic {}<{}>", symbol.identifier(), generic.parameters().join(", "));
+                (section("Generics"), entry)
+            }
+            SymbolKind::Alias(alias) => {
+                let mut entry = Entry::new("type", symbol);
+                entry.fact("Target", self.type_text(alias.target()));
+                (section("Type aliases"), entry)
+            }
+            SymbolKind::Constant(constant) => {
+                let mut entry = Entry::new("const", symbol);
+                entry.fact("Type", self.type_text(constant.ty()));
+                entry.fact("Value", Text::code(constant.value()));
+                (section("Constants"), entry)
+            }
+            _ => return None,
+        };
+
+        Some(result)
+    }
+}
+
+fn constraint_texts(constraints: &Constraints) -> Vec<Text> {
+    let mut texts = vec![];
+    if let Some(length) = constraints.length() {
+        texts.push(Text::code(format!("@len({length})")));
+    }
+    if let Some(range) = constraints.range() {
+        texts.push(Text::code(format!("@range({range})")));
+    }
+    if let Some(pattern) = constraints.pattern() {
+        let mut text = Text::code(format!("@pattern \"{}\"", pattern.source()));
+        if !pattern.is_anchored() {
+            text.push_plain(" ");
+            text.push_code("@unanchored");
+        }
+        texts.push(text);
+    }
+    texts
+}
+
+/// The `#[Name(value)]` attributes of a symbol, sorted by name.
+fn metadata(metadata: &std::collections::HashMap<String, Option<BaseType>>) -> Text {
+    let mut attributes = metadata.iter().collect::<Vec<_>>();
+    attributes.sort_by_key(|(name, _)| name.as_str());
+    let attributes = attributes.into_iter().map(|(name, value)| match value {
+        Some(BaseType::Number(value) | BaseType::String(value) | BaseType::Ident(value)) => {
+            Text::code(format!("{name}({value})"))
+        }
+        Some(BaseType::Boolean(value)) => Text::code(format!("{name}({value})")),
+        None => Text::code(name),
+    });
+    Text::join(attributes, ", ")
+}
+
+/// Writes a page in the requested format. Every page links to the pages of the other namespaces.
+struct Printer<'a> {
+    page: &'a Page,
+    pages: &'a [Page],
+    format: DocFormat,
+}
+
+impl Printer<'_> {
+    fn href(&self, page: Option<&str>, anchor: &str) -> String {
+        let extension = match self.format {
+            DocFormat::Markdown => "md",
+            DocFormat::Html => "html",
+        };
+        match page {
+            Some(page) => format!("{page}.{extension}#{anchor}"),
+            None => format!("#{anchor}"),
+        }
+    }
+
+    /// Writes inline text. Table cells can't contain `|` in Markdown.
+    fn write_text(&self, f: &mut Formatter<'_>, text: &Text, cell: bool) -> std::fmt::Result {
+        let markdown = |value: &str| {
+            if cell {
+                value.replace('|', "\\|")
+            } else {
+                value.to_string()
+            }
+        };
+        for span in &text.0 {
+            match (self.format, span) {
+                (DocFormat::Markdown, Span::Plain(value)) => write!(f, "{}", markdown(value))?,
+                (DocFormat::Markdown, Span::Code(value)) => write!(f, "`{}`", markdown(value))?,
+                (DocFormat::Markdown, Span::Link { text, page, anchor }) => {
+                    write!(f, "[`{}`]({})", markdown(text), self.href(page.as_deref(), anchor))?;
+                }
+                (DocFormat::Html, Span::Plain(value)) => write!(f, "{}", escape(value))?,
+                (DocFormat::Html, Span::Code(value)) => write!(f, "<code>{}</code>", escape(value))?,
+                (DocFormat::Html, Span::Link { text, page, anchor }) => write!(
+                    f,
+                    "<a href=\"{}\"><code>{}</code></a>",
+                    escape(&self.href(page.as_deref(), anchor)),
+                    escape(text)
+                )?,
+            }
+        }
+        Ok(())
+    }
+
+    fn navigation(&self) -> Vec<(String, String)> {
+        self.pages
+            .iter()
+            .map(|page| {
+                let name = if page.namespace.is_empty() {
+                    "global"
+                } else {
+                    &page.namespace
+                };
+                (name.to_string(), page.file_name(self.format))
+            })
+            .collect()
+    }
+
+    fn write_markdown(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# {}", self.page.title())?;
+        writeln!(f)?;
+        let navigation = self.navigation();
+        if navigation.len() > 1 {
+            let links = navigation
+                .iter()
+                .map(|(name, file)| format!("[{name}]({file})"))
+                .collect::<Vec<_>>();
+            writeln!(f, "Namespaces: {}", links.join(" · "))?;
+            writeln!(f)?;
+        }
+
+        for (title, entries) in &self.page.sections {
+            writeln!(f, "## {title}")?;
+            writeln!(f)?;
+            for entry in entries {
+                writeln!(f, "<a id=\"{}\"></a>", entry.anchor)?;
+                writeln!(f)?;
+                writeln!(f, "### `{}`", entry.title)?;
+                writeln!(f)?;
+                if let Some(doc) = &entry.doc {
+                    writeln!(f, "{doc}")?;
+                    writeln!(f)?;
+                }
+                for (name, text) in &entry.facts {
+                    write!(f, "- **{name}:** ")?;
+                    self.write_text(f, text, false)?;
+                    writeln!(f)?;
+                }
+                if !entry.facts.is_empty() {
+                    writeln!(f)?;
+                }
+                if !entry.rows.is_empty() {
+                    writeln!(f, "| {} |", entry.columns.join(" | "))?;
+                    writeln!(f, "|{}", "---|".repeat(entry.columns.len()))?;
+                    for row in &entry.rows {
+                        write!(f, "|")?;
+                        for cell in row {
+                            write!(f, " ")?;
+                            self.write_text(f, cell, true)?;
+                            write!(f, " |")?;
+                        }
+                        writeln!(f)?;
+                    }
+                    writeln!(f)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_html(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let title = escape(&self.page.title());
+        writeln!(f, "<!DOCTYPE html>")?;
+        writeln!(f, "<html>")?;
+        writeln!(f, "<head>")?;
+        writeln!(f, "<meta charset=\"utf-8\">")?;
+        writeln!(f, "<title>{title}</title>")?;
+        writeln!(f, "<style>")?;
+        writeln!(
+            f,
+            "body {{ font-family: sans-serif; max-width: 60em; margin: auto; padding: 1em; }}"
+        )?;
+        writeln!(f, "table {{ border-collapse: collapse; }}")?;
+        writeln!(
+            f,
+            "th, td {{ border: 1px solid #ccc; padding: 0.25em 0.5em; text-align: left; }}"
+        )?;
+        writeln!(f, "</style>")?;
+        writeln!(f, "</head>")?;
+        writeln!(f, "<body>")?;
+        let navigation = self.navigation();
+        if navigation.len() > 1 {
+            let links = navigation
+                .iter()
+                .map(|(name, file)| format!("<a href=\"{}\">{}</a>", escape(file), escape(name)))
+                .collect::<Vec<_>>();
+            writeln!(f, "<nav>Namespaces: {}</nav>", links.join(" · "))?;
+        }
+        writeln!(f, "<h1>{title}</h1>")?;
+
+        for (section, entries) in &self.page.sections {
+            writeln!(f, "<h2>{section}</h2>")?;
+            for entry in entries {
+                writeln!(
+                    f,
+                    "<h3 id=\"{}\"><code>{}</code></h3>",
+                    escape(&entry.anchor),
+                    escape(&entry.title)
+                )?;
+                for paragraph in entry.doc.iter().flat_map(|doc| doc.split("\n\n")) {
+                    writeln!(f, "<p>{}</p>", escape(paragraph))?;
+                }
+                if !entry.facts.is_empty() {
+                    writeln!(f, "<dl>")?;
+                    for (name, text) in &entry.facts {
+                        write!(f, "<dt>{name}</dt><dd>")?;
+                        self.write_text(f, text, false)?;
+                        writeln!(f, "</dd>")?;
+                    }
+                    writeln!(f, "</dl>")?;
+                }
+                if !entry.rows.is_empty() {
+                    writeln!(f, "<table>")?;
+                    let headers = entry
+                        .columns
+                        .iter()
+                        .map(|c| format!("<th>{c}</th>"))
+                        .collect::<String>();
+                    writeln!(f, "<tr>{headers}</tr>")?;
+                    for row in &entry.rows {
+                        write!(f, "<tr>")?;
+                        for cell in row {
+                            write!(f, "<td>")?;
+                            self.write_text(f, cell, true)?;
+                            write!(f, "</td>")?;
+                        }
+                        writeln!(f, "</tr>")?;
+                    }
+                    writeln!(f, "</table>")?;
+                }
+            }
+        }
+
+        writeln!(f, "</body>")?;
+        writeln!(f, "</html>")
+    }
+}
+
+impl Display for Printer<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.format {
+            DocFormat::Markdown => self.write_markdown(f),
+            DocFormat::Html => self.write_html(f),
+        }
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,214 @@
+use crate::diagnostic::Diagnostic;
+use crate::{SourceKind, to_file_url};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::process::ExitCode;
+use type_ml_definitions::{AnalysisWorkspace, Constraints, SchemaModel, Symbol, SymbolKind, SymbolRef};
+
+pub fn run(path: &Path) -> ExitCode {
+    if SourceKind::from_path(path) != Some(SourceKind::Schema) {
+        Diagnostic::new(path, "Expected a .tmd file").emit();
+        return ExitCode::FAILURE;
+    }
+
+    let model = to_file_url(path).and_then(|url| AnalysisWorkspace::new(url).run().map_err(|e| e.to_string()));
+    match model {
+        Ok(model) => {
+            print!("{}", SchemaDump(&model));
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            Diagnostic::new(path, message).emit();
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn type_name(model: &SchemaModel, symbol_ref: SymbolRef) -> String {
+    let identifier = model.get_type_by_ref(symbol_ref).as_ref().identifier().to_string();
+    if symbol_ref.namespace == 0 {
+        identifier
+    } else {
+        format!("{}::{identifier}", model.namespaces()[symbol_ref.namespace])
+    }
+}
+
+fn type_list(model: &SchemaModel, refs: &[SymbolRef]) -> String {
+    refs.iter().map(|r| type_name(model, *r)).collect::<Vec<_>>().join(", ")
+}
+
+fn constraints(value: &Constraints) -> String {
+    let mut annotations = String::new();
+    if let Some(length) = value.length() {
+        annotations.push_str(&format!("@len({length}) "));
+    }
+    if let Some(range) = value.range() {
+        annotations.push_str(&format!("@range({range}) "));
+    }
+    if let Some(pattern) = value.pattern() {
+        annotations.push_str(&format!("@pattern \"{}\" ", pattern.source()));
+        if !pattern.is_anchored() {
+            annotations.push_str("@unanchored ");
+        }
+    }
+    annotations
+}
+
+/// Writes a `///` comment line by line, indented by `indent`.
+fn doc(f: &mut Formatter<'_>, indent: &str, doc: Option<&str>) -> std::fmt::Result {
+    for line in doc.into_iter().flat_map(str::lines) {
+        writeln!(f, "{indent}/// {line}")?;
+    }
+    Ok(())
+}
+
+fn default(value: Option<&str>) -> String {
+    value.map(|v| format!(" = {v}")).unwrap_or_default()
+}
+
+/// Prints every namespace of the model with its symbols in a .tmd-like notation.
+pub struct SchemaDump<'a>(pub &'a SchemaModel);
+
+impl Display for SchemaDump<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let model = self.0;
+        for (namespace, symbols) in model.namespaces().iter().zip(model.modules()) {
+            if namespace.is_empty() {
+                writeln!(f, "// namespace: (global)")?;
+            } else {
+                writeln!(f, "// namespace: {namespace}")?;
+            }
+
+            for symbol in symbols {
+                dump_symbol(f, model, symbol)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn dump_symbol(f: &mut Formatter<'_>, model: &SchemaModel, symbol: &SymbolKind) -> std::fmt::Result {
+    let identifier = symbol.identifier();
+    doc(f, "", symbol.doc())?;
+    match symbol {
+        SymbolKind::Generic(generic) => writeln!(f, "generic {identifier}<{}>;", generic.parameters().join(", ")),
+        SymbolKind::Array(_) => writeln!(f, "array {identifier};"),
+        SymbolKind::Option(_) => writeln!(f, "option {identifier};"),
+        SymbolKind::Map(_) => writeln!(f, "map {identifier};"),
+        SymbolKind::Alias(alias) => writeln!(f, "type {identifier} = {};", type_name(model, alias.target())),
+        SymbolKind::Constant(constant) => writeln!(
+            f,
+            "const {identifier}: {} = {};",
+            type_name(model, constant.ty()),
+            constant.value()
+        ),
+        SymbolKind::Lazy(_) => Ok(()),
+        SymbolKind::Struct(symbol) => {
+            writeln!(f, "struct {identifier} {{")?;
+            for field in &symbol.fields {
+                doc(f, "    ", field.doc())?;
+                let ty = type_name(model, field.ty());
+                let constraints = constraints(field.constraints());
+                writeln!(
+                    f,
+                    "    {constraints}{}: {ty}{},",
+                    field.identifier(),
+                    default(field.default())
+                )?;
+            }
+            writeln!(f, "}}")
+        }
+        SymbolKind::Enum(symbol) => {
+            writeln!(f, "enum {identifier} {{")?;
+            for variant in &symbol.variants {
+                doc(f, "    ", variant.doc.as_deref())?;
+                if let Some(pattern) = &variant.pattern {
+                    writeln!(f, "    @pattern \"{}\"", pattern.source())?;
+                    if !pattern.is_anchored() {
+                        writeln!(f, "    @unanchored")?;
+                    }
+                }
+                if let Some(ty) = &variant.ty {
+                    writeln!(f, "    {}({}),", variant.identifier, type_name(model, ty.as_concrete()))?;
+                } else {
+                    writeln!(f, "    {},", variant.identifier)?;
+                }
+            }
+            writeln!(f, "}}")
+        }
+        SymbolKind::Group(symbol) => {
+            if symbol.extend() {
+                writeln!(f, "@extend")?;
+            }
+            if symbol.groups().is_empty() {
+                return writeln!(f, "group {identifier};");
+            }
+            let ordered = if symbol.ordered() { " ordered" } else { "" };
+            writeln!(f, "group {identifier}{ordered} {{")?;
+            let mut printed_choices = Vec::new();
+            for entry in symbol.groups() {
+                if let Some(choice) = entry.choice() {
+                    if !printed_choices.contains(&choice) {
+                        printed_choices.push(choice);
+                        let alternatives = symbol
+                            .groups()
+                            .iter()
+                            .filter(|g| g.choice() == Some(choice))
+                            .map(|g| type_name(model, g.symbol()))
+                            .collect::<Vec<_>>();
+                        writeln!(f, "    + one_of({})", alternatives.join(", "))?;
+                    }
+                    continue;
+                }
+                let unique = if entry.unique() { "unique " } else { "" };
+                let count = entry.count().map(|c| format!("({c})")).unwrap_or_default();
+                writeln!(f, "    + {unique}{}{count}", type_name(model, entry.symbol()))?;
+            }
+            writeln!(f, "}}")
+        }
+        SymbolKind::Element(symbol) => {
+            let group = type_name(model, symbol.group());
+            if symbol.fields().is_empty() {
+                return writeln!(f, "element {identifier} -> {group};");
+            }
+            writeln!(f, "element {identifier} -> {group} {{")?;
+            for field in symbol.fields() {
+                doc(f, "    ", field.doc())?;
+                let ty = type_name(model, field.ty());
+                let required = if field.is_required() { "@required " } else { "" };
+                let constraints = constraints(field.constraints());
+                writeln!(
+                    f,
+                    "    {required}{constraints}{}: {ty}{},",
+                    field.identifier(),
+                    default(field.default())
+                )?;
+            }
+            writeln!(f, "}}")
+        }
+        SymbolKind::Expression(symbol) => {
+            if !symbol.groups().is_empty() {
+                writeln!(f, "@groups [{}]", type_list(model, symbol.groups()))?;
+            }
+            if !symbol.restrict().is_empty() {
+                writeln!(f, "@restrict [{}]", type_list(model, symbol.restrict()))?;
+            }
+            writeln!(f, "expression {identifier} {{")?;
+            for field in symbol.fields() {
+                doc(f, "    ", field.doc())?;
+                let optional = if field.is_optional() { "@optional " } else { "" };
+                let ty = type_name(model, field.ty());
+                let constraints = constraints(field.constraints());
+                writeln!(
+                    f,
+                    "    {optional}{constraints}{}: {ty}{},",
+                    field.identifier(),
+                    default(field.default())
+                )?;
+            }
+            writeln!(f, "}}")
+        }
+        _ => writeln!(f, "primitive {identifier};"),
+    }
+}
@@ -0,0 +1,315 @@
+use crate::diagnostic::Diagnostic;
+use crate::{ExportFormat, SourceKind, to_file_url};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::process::ExitCode;
+use type_ml::{LayoutModel, ResolvedBaseType, ResolvedElement, ResolvedType, RmlParser};
+
+pub fn run(path: &Path, format: ExportFormat, output: Option<&Path>) -> ExitCode {
+    if SourceKind::from_path(path) != Some(SourceKind::Layout) {
+        Diagnostic::new(path, "Expected a .tml file").emit();
+        return ExitCode::FAILURE;
+    }
+
+    let root = match resolve(path) {
+        Ok(root) => root,
+        Err(message) => {
+            Diagnostic::new(path, message).emit();
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let value = Value::from_element(&root);
+    let text = match format {
+        ExportFormat::Json => format!("{}\n", Printer::json(&value)),
+        ExportFormat::Ron => format!("{}\n", Printer::ron(&value)),
+    };
+
+    if let Some(output) = output {
+        if let Err(error) = std::fs::write(output, text) {
+            eprintln!("error: {}: {error}", output.display());
+            return ExitCode::FAILURE;
+        }
+    } else {
+        print!("{text}");
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn resolve(path: &Path) -> Result<ResolvedElement, String> {
+    let url = to_file_url(path)?;
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let ast = RmlParser::build_ast(&content).map_err(|e| e.to_string())?;
+    let path = url.to_file_path().expect("Unreachable!");
+    LayoutModel::resolve(ast, &path.to_string_lossy()).map_err(|e| e.to_string())
+}
+
+/// Format independent view of a resolved layout.
+enum Value {
+    Null,
+    /// A present optional value: `Some(value)` in RON, the bare value in JSON.
+    Some(Box<Value>),
+    Boolean(bool),
+    Number(String),
+    String(String),
+    /// An enum variant; RON writes identifiers unquoted.
+    Variant(String),
+    /// An enum variant with a payload: `Variant(value)` in RON, `{ "Variant": value }` in JSON.
+    Newtype(String, Box<Value>),
+    /// A record with identifier keys (RON struct).
+    Record(Vec<(String, Value)>),
+    /// A record with string keys (RON map).
+    Map(Vec<(String, Value)>),
+    List(Vec<Value>),
+}
+
+fn full_path(namespace: Option<&str>, identifier: &str) -> String {
+    match namespace {
+        Some(namespace) => format!("{namespace}::{identifier}"),
+        None => identifier.to_string(),
+    }
+}
+
+impl Value {
+    fn from_element(element: &ResolvedElement) -> Self {
+        let mut fields = vec![(
+            "element".to_string(),
+            Value::String(full_path(element.namespace(), element.identifier())),
+        )];
+
+        if !element.metadata().is_empty() {
+            fields.push(("metadata".to_string(), Value::from_metadata(element.metadata())));
+        }
+
+        let attributes = element
+            .attributes()
+            .iter()
+            .map(|a| (a.identifier().to_string(), Value::from_type(a.value())))
+            .collect();
+        fields.push(("attributes".to_string(), Value::Map(attributes)));
+
+        let children = element.children().iter().map(Value::from_element).collect();
+        fields.push(("children".to_string(), Value::List(children)));
+
+        Value::Record(fields)
+    }
+
+    fn from_metadata(metadata: &HashMap<String, Option<ResolvedBaseType>>) -> Self {
+        let mut entries = metadata
+            .iter()
+            .map(|(name, value)| (name.clone(), value.as_ref().map_or(Value::Null, Value::from_base)))
+            .collect::<Vec<_>>();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Value::Map(entries)
+    }
+
+    fn from_base(value: &ResolvedBaseType) -> Self {
+        match value {
+            ResolvedBaseType::F32(v) => Value::Number(format!("{v:?}")),
+            ResolvedBaseType::F64(v) => Value::Number(format!("{v:?}")),
+            ResolvedBaseType::I8(v) => Value::Number(v.to_string()),
+            ResolvedBaseType::I16(v) => Value::Number(v.to_string()),
+            ResolvedBaseType::I32(v) => Value::Number(v.to_string()),
+            ResolvedBaseType::I64(v) => Value::Number(v.to_string()),
+            ResolvedBaseType::U8(v) => Value::Number(v.to_string()),
+            ResolvedBaseType::U16(v) => Value::Number(v.to_string()),
+            ResolvedBaseType::U32(v) => Value::Number(v.to_string()),
+            ResolvedBaseType::U64(v) => Value::Number(v.to_string()),
+            ResolvedBaseType::I128(v) => Value::Number(v.to_string()),
+            ResolvedBaseType::U128(v) => Value::Number(v.to_string()),
+            ResolvedBaseType::Isize(v) => Value::Number(v.to_string()),
+            ResolvedBaseType::Usize(v) => Value::Number(v.to_string()),
+            ResolvedBaseType::Boolean(v) => Value::Boolean(*v),
+            ResolvedBaseType::Char(v) => Value::String(v.to_string()),
+            ResolvedBaseType::String(v) => Value::String(v.clone()),
+            ResolvedBaseType::Color(v) => Value::String(v.to_string()),
+            ResolvedBaseType::Path(v) => Value::String(v.to_string_lossy().into_owned()),
+            ResolvedBaseType::Url(v) => Value::String(v.to_string()),
+        }
+    }
+
+    /// The text of a map key: a string key without quotes or the identifier of an enum key.
+    fn key(key: &ResolvedType) -> String {
+        match key {
+            ResolvedType::Base(ResolvedBaseType::String(key)) => key.clone(),
+            ResolvedType::Enum(variant) => variant.identifier().to_string(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn from_type(value: &ResolvedType) -> Self {
+        match value {
+            ResolvedType::Base(base) => Value::from_base(base),
+            ResolvedType::Enum(variant) => match variant.payload() {
+                Some(payload) => Value::Newtype(variant.identifier().to_string(), Box::new(Value::from_type(payload))),
+                None => Value::Variant(variant.identifier().to_string()),
+            },
+            ResolvedType::Struct(value) => Value::Record(
+                value
+                    .fields()
+                    .iter()
+                    .map(|f| (f.identifier().to_string(), Value::from_type(f.value())))
+                    .collect(),
+            ),
+            ResolvedType::Expression(expr) => Value::Record(vec![
+                (
+                    "expression".to_string(),
+                    Value::String(full_path(expr.namespace(), expr.identifier())),
+                ),
+                (
+                    "arguments".to_string(),
+                    Value::Map(
+                        expr.arguments()
+                            .iter()
+                            .map(|a| (a.identifier().to_string(), Value::from_type(a.value())))
+                            .collect(),
+                    ),
+                ),
+            ]),
+            ResolvedType::List(values) => Value::List(values.iter().map(Value::from_type).collect()),
+            ResolvedType::Option(Some(value)) => Value::Some(Box::new(Value::from_type(value))),
+            ResolvedType::Option(None) => Value::Null,
+            ResolvedType::Map(entries) => Value::Map(
+                entries
+                    .iter()
+                    .map(|e| (Value::key(e.key()), Value::from_type(e.value())))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+fn is_identifier(value: &str) -> bool {
+    let mut chars = value.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Syntax {
+    Json,
+    Ron,
+}
+
+struct Printer<'a> {
+    value: &'a Value,
+    syntax: Syntax,
+}
+
+impl<'a> Printer<'a> {
+    fn json(value: &'a Value) -> Self {
+        Self {
+            value,
+            syntax: Syntax::Json,
+        }
+    }
+
+    fn ron(value: &'a Value) -> Self {
+        Self {
+            value,
+            syntax: Syntax::Ron,
+        }
+    }
+
+    fn write_string(f: &mut Formatter<'_>, value: &str) -> std::fmt::Result {
+        write!(f, "\"")?;
+        for c in value.chars() {
+            match c {
+                '"' => write!(f, "\\\"")?,
+                '\\' => write!(f, "\\\\")?,
+                '\n' => write!(f, "\\n")?,
+                '\r' => write!(f, "\\r")?,
+                '\t' => write!(f, "\\t")?,
+                c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+                c => write!(f, "{c}")?,
+            }
+        }
+        write!(f, "\"")
+    }
+
+    fn write_entries(
+        &self,
+        f: &mut Formatter<'_>,
+        entries: &[(String, Value)],
+        (open, close): (&str, &str),
+        quote_keys: bool,
+        depth: usize,
+    ) -> std::fmt::Result {
+        if entries.is_empty() {
+            return write!(f, "{open}{close}");
+        }
+
+        writeln!(f, "{open}")?;
+        for (i, (key, value)) in entries.iter().enumerate() {
+            write!(f, "{}", "    ".repeat(depth + 1))?;
+            if quote_keys {
+                Self::write_string(f, key)?;
+            } else {
+                write!(f, "{key}")?;
+            }
+            write!(f, ": ")?;
+            self.write_value(f, value, depth + 1)?;
+            if i + 1 < entries.len() || self.syntax == Syntax::Ron {
+                write!(f, ",")?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "{}{close}", "    ".repeat(depth))
+    }
+
+    fn write_value(&self, f: &mut Formatter<'_>, value: &Value, depth: usize) -> std::fmt::Result {
+        let json = self.syntax == Syntax::Json;
+        match value {
+            Value::Null => write!(f, "{}", if json { "null" } else { "None" }),
+            Value::Some(value) if json => self.write_value(f, value, depth),
+            Value::Some(value) => {
+                write!(f, "Some(")?;
+                self.write_value(f, value, depth)?;
+                write!(f, ")")
+            }
+            Value::Boolean(value) => write!(f, "{value}"),
+            Value::Number(value) => write!(f, "{value}"),
+            Value::String(value) => Self::write_string(f, value),
+            Value::Variant(value) if !json && is_identifier(value) => write!(f, "{value}"),
+            Value::Variant(value) => Self::write_string(f, value),
+            Value::Newtype(variant, value) if json => {
+                write!(f, "{{ ")?;
+                Self::write_string(f, variant)?;
+                write!(f, ": ")?;
+                self.write_value(f, value, depth)?;
+                write!(f, " }}")
+            }
+            Value::Newtype(variant, value) => {
+                write!(f, "{variant}(")?;
+                self.write_value(f, value, depth)?;
+                write!(f, ")")
+            }
+            Value::Record(entries) if json => self.write_entries(f, entries, ("{", "}"), true, depth),
+            Value::Record(entries) => self.write_entries(f, entries, ("(", ")"), false, depth),
+            Value::Map(entries) => self.write_entries(f, entries, ("{", "}"), true, depth),
+            Value::List(values) => {
+                if values.is_empty() {
+                    return write!(f, "[]");
+                }
+                writeln!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    write!(f, "{}", "    ".repeat(depth + 1))?;
+                    self.write_value(f, value, depth + 1)?;
+                    if i + 1 < values.len() || !json {
+                        write!(f, ",")?;
+                    }
+                    writeln!(f)?;
+                }
+                write!(f, "{}]", "    ".repeat(depth))
+            }
+        }
+    }
+}
+
+impl Display for Printer<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write_value(f, self.value, 0)
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{SourceKind, collect_files};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const INDENT: &str = "    ";
+
+pub fn run(paths: &[PathBuf], check: bool) -> ExitCode {
+    let files = match collect_files(paths) {
+        Ok(files) => files,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut unformatted = 0;
+    for file in &files {
+        let kind = SourceKind::from_path(file).expect("Unreachable!");
+        let source = match std::fs::read_to_string(file) {
+            Ok(source) => source,
+            Err(error) => {
+                eprintln!("error: {}: {error}", file.display());
+                return ExitCode::FAILURE;
+            }
+        };
+
+        let formatted = format_source(&source, kind);
+        if formatted == source {
+            continue;
+        }
+
+        if check {
+            println!("{} is not formatted", file.display());
+            unformatted += 1;
+        } else if let Err(error) = std::fs::write(file, formatted) {
+            eprintln!("error: {}: {error}", file.display());
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if unformatted == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Tracks bracket and tag nesting across lines, skipping strings and comments.
+#[derive(Default)]
+struct Scanner {
+    depth: usize,
+    in_string: bool,
+    in_comment: bool,
+    in_open_tag: bool,
+    in_close_tag: bool,
+    /// `{{` nesting inside the current open tag. The outermost pair shares the indent level of the tag.
+    tag_braces: usize,
+}
+
+impl Scanner {
+    /// Consumes one line and returns the depth its first token should be indented at.
+    fn scan_line(&mut self, line: &str, kind: SourceKind) -> usize {
+        let chars = line.chars().collect::<Vec<_>>();
+        let directive = line.starts_with('#') && !line.starts_with("#[");
+        let mut indent = None;
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let next = chars.get(i + 1).copied();
+
+            if self.in_comment {
+                if c == '*' && next == Some('/') {
+                    self.in_comment = false;
+                    i += 1;
+                }
+                i += 1;
+                continue;
+            }
+
+            if self.in_string {
+                if c == '"' {
+                    self.in_string = false;
+                }
+                i += 1;
+                continue;
+            }
+
+            let before = self.depth;
+            let closing = match (c, next) {
+                ('/', Some('/')) => break,
+                ('/', Some('*')) => {
+                    indent.get_or_insert(before);
+                    self.in_comment = true;
+                    i += 2;
+                    continue;
+                }
+                ('"', _) => {
+                    self.in_string = true;
+                    false
+                }
+                ('-', Some('>')) => {
+                    i += 1;
+                    false
+                }
+                ('{', Some('{')) if kind == SourceKind::Layout => {
+                    i += 1;
+                    if self.in_open_tag {
+                        self.tag_braces += 1;
+                    }
+                    // The outermost `{{` of a tag shares its level, the tag already indents the fields
+                    if self.tag_braces != 1 {
+                        self.step(false);
+                    }
+                    false
+                }
+                ('}', Some('}')) if kind == SourceKind::Layout => {
+                    i += 1;
+                    if self.tag_braces == 1 {
+                        self.tag_braces = 0;
+                        // Dedent the line like a closing bracket, the tag keeps its level
+                        if indent.is_none() {
+                            indent = Some(before.saturating_sub(1));
+                        }
+                        i += 1;
+                        continue;
+                    }
+                    self.tag_braces = self.tag_braces.saturating_sub(1);
+                    self.step(true)
+                }
+                ('{' | '[' | '(', _) => self.step(false),
+                ('}' | ']' | ')', _) => self.step(true),
+                ('<', Some('/')) if kind == SourceKind::Layout && !directive => {
+                    self.in_close_tag = true;
+                    i += 1;
+                    self.step(true)
+                }
+                ('<', Some(n)) if kind == SourceKind::Layout && !directive && (n.is_alphabetic() || n == '_') => {
+                    self.in_open_tag = true;
+                    self.step(false)
+                }
+                ('/', Some('>')) if self.in_open_tag => {
+                    self.in_open_tag = false;
+                    i += 1;
+                    self.step(true)
+                }
+                ('>', _) if self.in_open_tag => {
+                    self.in_open_tag = false;
+                    false
+                }
+                ('>', _) if self.in_close_tag => {
+                    self.in_close_tag = false;
+                    true
+                }
+                _ => false,
+            };
+
+            if indent.is_none() && !closing && !c.is_whitespace() {
+                indent = Some(if c == '>' { before.saturating_sub(1) } else { before });
+            }
+
+            i += 1;
+        }
+
+        indent.unwrap_or(self.depth)
+    }
+
+    fn step(&mut self, close: bool) -> bool {
+        if close {
+            self.depth = self.depth.saturating_sub(1);
+        } else {
+            self.depth += 1;
+        }
+        close
+    }
+}
+
+/// Normalizes indentation to four spaces per nesting level, strips trailing whitespace,
+/// and collapses consecutive blank lines. A file keeps its final newline, or the lack of it.
+/// Lines that continue a multi-line string or block comment are left untouched.
+pub fn format_source(source: &str, kind: SourceKind) -> String {
+    let mut scanner = Scanner::default();
+    let mut output = String::with_capacity(source.len());
+    let mut blank = false;
+
+    for line in source.lines() {
+        if scanner.in_string || scanner.in_comment {
+            scanner.scan_line(line, kind);
+            output.push_str(line.trim_end_matches([' ', '\t', '\r']));
+            output.push('\n');
+            continue;
+        }
+
+        let line = line.trim();
+        if line.is_empty() {
+            blank = !output.is_empty();
+            continue;
+        }
+
+        if blank {
+            output.push('\n');
+            blank = false;
+        }
+
+        let depth = scanner.scan_line(line, kind);
+        output.push_str(&INDENT.repeat(depth));
+        output.push_str(line);
+        output.push('\n');
+    }
+
+    if !source.ends_with('\n') {
+        output.pop();
+    }
+    output
+}
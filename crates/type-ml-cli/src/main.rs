@@ -0,0 +1,150 @@
+mod check;
+mod diagnostic;
+mod docs;
+mod dump;
+mod export;
+mod fmt;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use url::Url;
+
+#[derive(Parser)]
+#[command(name = "type-ml", version, about = "Validate, format and inspect TypeML files")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Validate .tml layouts and .tmd schemas
+    Check {
+        /// Files or directories to check
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// Keep running and revalidate the layouts affected by every change
+        #[arg(short, long)]
+        watch: bool,
+    },
+    /// Normalize indentation and whitespace of .tml and .tmd files
+    Fmt {
+        /// Files or directories to format
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// Report unformatted files instead of rewriting them
+        #[arg(long)]
+        check: bool,
+    },
+    /// Print the namespaces and symbols of a resolved schema
+    DumpSchema {
+        /// The .tmd file to load
+        path: PathBuf,
+    },
+    /// Validate a layout and print its resolved tree
+    Export {
+        /// The .tml file to export
+        path: PathBuf,
+        #[arg(short, long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+        /// Write the result to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Generate reference documentation with a page per namespace of a schema
+    Docs {
+        /// The .tmd file to document
+        path: PathBuf,
+        #[arg(short, long, value_enum, default_value_t = DocFormat::Markdown)]
+        format: DocFormat,
+        /// The directory to write the pages to
+        #[arg(short, long, default_value = "docs")]
+        output: PathBuf,
+    },
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum ExportFormat {
+    Json,
+    Ron,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum DocFormat {
+    Markdown,
+    Html,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum SourceKind {
+    Layout,
+    Schema,
+}
+
+impl SourceKind {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("tml") => Some(SourceKind::Layout),
+            Some("tmd") => Some(SourceKind::Schema),
+            _ => None,
+        }
+    }
+}
+
+/// Expands directories into the .tml and .tmd files they contain, sorted by path. Paths given on the command line
+/// must exist and be sources or directories with sources in them; other files are only skipped inside directories.
+fn collect_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let found = walk(path).map_err(|e| format!("{}: {e}", path.display()))?;
+            if found.is_empty() {
+                return Err(format!("{}: No .tml or .tmd files found", path.display()));
+            }
+            files.extend(found);
+        } else if let Err(error) = path.metadata() {
+            return Err(format!("{}: {error}", path.display()));
+        } else if SourceKind::from_path(path).is_some() {
+            files.push(path.clone());
+        } else {
+            return Err(format!("{}: Expected a .tml or .tmd file", path.display()));
+        }
+    }
+
+    Ok(files)
+}
+
+fn walk(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut entries = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    let mut files = Vec::new();
+    for entry in entries {
+        if entry.is_dir() {
+            files.extend(walk(&entry)?);
+        } else if SourceKind::from_path(&entry).is_some() {
+            files.push(entry);
+        }
+    }
+    Ok(files)
+}
+
+fn to_file_url(path: &Path) -> Result<Url, String> {
+    let path = path.canonicalize().map_err(|e| format!("{}: {e}", path.display()))?;
+    Url::from_file_path(&path).map_err(|()| format!("Invalid path: {}", path.display()))
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Check { paths, watch: false } => check::run(&paths),
+        Command::Check { paths, watch: true } => check::watch(&paths),
+        Command::Fmt { paths, check } => fmt::run(&paths, check),
+        Command::DumpSchema { path } => dump::run(&path),
+        Command::Export { path, format, output } => export::run(&path, format, output.as_deref()),
+        Command::Docs { path, format, output } => docs::run(&path, format, &output),
+    }
+}
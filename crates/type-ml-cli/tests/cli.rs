@@ -0,0 +1,273 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::process::Output;
+
+    fn example(path: &str) -> String {
+        format!("{}examples/{path}", env!("CARGO_WORKSPACE_DIR"))
+    }
+
+    fn run(args: &[&str]) -> Output {
+        std::process::Command::new(env!("CARGO_BIN_EXE_type-ml"))
+            .args(args)
+            .output()
+            .unwrap()
+    }
+
+    fn stdout(args: &[&str]) -> String {
+        let output = run(args);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    /// A fresh directory for the files a test writes.
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("type-ml-cli-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn check_directory() {
+        let dir = scratch("check_directory");
+        std::fs::create_dir(dir.join("ui")).unwrap();
+        std::fs::write(dir.join("ui/definitions.tmd"), "group Root {}\n").unwrap();
+        std::fs::write(dir.join("ui/notes.txt"), "").unwrap();
+        let output = stdout(&["check", dir.to_str().unwrap()]);
+        assert!(output.contains("Checked 1 file(s): 0 with errors"), "{output}");
+    }
+
+    #[test]
+    fn check_rejects_missing_and_unsupported_paths() {
+        let dir = scratch("check_paths");
+        let text = dir.join("notes.txt");
+        std::fs::write(&text, "").unwrap();
+        let missing = dir.join("missing");
+        for path in [&missing, &text, &dir] {
+            let output = run(&["check", path.to_str().unwrap()]);
+            assert!(!output.status.success(), "{}", path.display());
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            assert!(stderr.contains(&*path.to_string_lossy()), "{stderr}");
+        }
+
+        let output = run(&["fmt", "--check", missing.to_str().unwrap()]);
+        assert!(!output.status.success());
+    }
+
+    #[test]
+    fn fmt_layout() {
+        let path = scratch("fmt_layout").join("layout.tml");
+        let source = "<Root>\n<Node border={{\nleft: 2px,\n  right: 2px,\n}}/>\n\n\n<Text value=\"a\"/>\n</Root>\n";
+        std::fs::write(&path, source).unwrap();
+        stdout(&["fmt", path.to_str().unwrap()]);
+
+        let expected = "<Root>\n    <Node border={{\n        left: 2px,\n        right: 2px,\n    }}/>\n\n    <Text value=\"a\"/>\n</Root>\n";
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), expected);
+    }
+
+    #[test]
+    fn fmt_check_examples() {
+        for path in [
+            "layout.tml",
+            "base.tmd",
+            "option",
+            "errors/0_unresolved_type",
+            "errors/7_parse_bool",
+        ] {
+            let output = run(&["fmt", "--check", &example(path)]);
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+        }
+    }
+
+    #[test]
+    fn fmt_check_reports_unformatted() {
+        let path = scratch("fmt_check").join("definitions.tmd");
+        std::fs::write(&path, "struct A {\na: f32,\n}").unwrap();
+        let output = run(&["fmt", "--check", path.to_str().unwrap()]);
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stdout).contains("is not formatted"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "struct A {\na: f32,\n}");
+    }
+
+    #[test]
+    fn export_json() {
+        let json = stdout(&["export", &example("layout.tml")]);
+        assert!(json.starts_with("{\n    \"element\": \"base::Layout\","), "{json}");
+        assert!(json.contains("\"width\": { \"Percent\": 100.0 },"), "{json}");
+        assert!(json.contains("\"Path\": \"bevy::prelude::Node\""), "{json}");
+    }
+
+    #[test]
+    fn export_ron() {
+        let ron = stdout(&["export", &example("layout.tml"), "-f", "ron"]);
+        assert!(ron.contains("element: \"base::Layout\","), "{ron}");
+        assert!(ron.contains("\"width\": Percent(100.0),"), "{ron}");
+        assert!(ron.contains("left: Px(10.0),"), "{ron}");
+    }
+
+    #[test]
+    fn export_to_file() {
+        let path = scratch("export").join("layout.json");
+        stdout(&["export", &example("layout.tml"), "-o", path.to_str().unwrap()]);
+        assert!(
+            std::fs::read_to_string(&path)
+                .unwrap()
+                .contains("\"element\": \"base::Layout\"")
+        );
+    }
+
+    #[test]
+    fn export_options() {
+        let json = stdout(&["export", &example("option/layout.tml")]);
+        assert!(json.starts_with("{\n    \"element\": \"Image\","), "{json}");
+        assert!(json.contains("\"fit\": null,"), "{json}");
+        assert!(json.contains("\"width\": 10.0,"), "{json}");
+
+        let ron = stdout(&["export", &example("option/layout.tml"), "-f", "ron"]);
+        assert!(ron.contains("\"fit\": None,"), "{ron}");
+        assert!(ron.contains("\"scale\": Some(1.5),"), "{ron}");
+        assert!(ron.contains("\"size\": Some((\n            width: 10.0,"), "{ron}");
+    }
+
+    #[test]
+    fn export_rejects_schema() {
+        let output = run(&["export", &example("base.tmd")]);
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Expected a .tml file"));
+    }
+
+    #[test]
+    fn dump_schema() {
+        let dump = stdout(&["dump-schema", &example("schema.tmd")]);
+        assert!(dump.starts_with("// namespace: (global)\n"), "{dump}");
+        assert!(
+            dump.contains("enum FlexWrap {\n    NoWrap,\n    Wrap,\n    WrapReverse,\n}\n"),
+            "{dump}"
+        );
+        assert!(dump.contains("group Main {\n    + unique base::Root(1)\n}\n"), "{dump}");
+    }
+
+    #[test]
+    fn dump_aliases_and_constants() {
+        let dump = stdout(&["dump-schema", &example("constants/definitions.tmd")]);
+        assert!(dump.contains("type Length = Val;\n"), "{dump}");
+        assert!(dump.contains("const SPACING: Val = 8px;\n"), "{dump}");
+        assert!(dump.contains("    left: Val = GAP,\n"), "{dump}");
+    }
+
+    #[test]
+    fn dump_doc_comments() {
+        let dump = stdout(&["dump-schema", &example("docs/definitions.tmd")]);
+        assert!(
+            dump.contains("/// Space around the content.\ntype Spacing = Insets;\n"),
+            "{dump}"
+        );
+        assert!(
+            dump.contains("    /// Space on the left,\n    /// in pixels.\n    left: f32 = 0,\n"),
+            "{dump}"
+        );
+        assert!(
+            dump.contains("    /// The main axis.\n    @required direction: Direction,\n"),
+            "{dump}"
+        );
+    }
+
+    #[test]
+    fn dump_namespaces() {
+        let dump = stdout(&["dump-schema", &example("schema.tmd")]);
+        assert!(dump.contains("// namespace: base\n"), "{dump}");
+        assert!(dump.contains("element Layout -> base::Root;\n"), "{dump}");
+    }
+
+    /// Writes the documentation of `schema` to a scratch directory and reads the page of `namespace`.
+    fn docs(name: &str, schema: &str, format: &str, page: &str) -> String {
+        let dir = scratch(name);
+        stdout(&["docs", &example(schema), "-f", format, "-o", dir.to_str().unwrap()]);
+        std::fs::read_to_string(dir.join(page)).unwrap()
+    }
+
+    #[test]
+    fn docs_markdown() {
+        let page = docs("docs_markdown", "schema.tmd", "markdown", "index.md");
+        assert!(
+            page.contains("- **Group:** [`base::Components`](base.md#Components)\n"),
+            "{page}"
+        );
+        assert!(
+            page.contains("| Variant | Payload | Pattern | Description |\n"),
+            "{page}"
+        );
+        assert!(
+            page.contains("| `Px` | `f32` | `([0-9]+(?:\\.[0-9]+)?)px` |  |\n"),
+            "{page}"
+        );
+        assert!(page.contains("| `Auto` |  |  |  |\n"), "{page}");
+
+        let page = docs("docs_markdown_base", "schema.tmd", "markdown", "base.md");
+        assert!(
+            page.contains("Namespaces: [global](index.md) · [base](base.md)\n"),
+            "{page}"
+        );
+        assert!(
+            page.contains(
+                "- **Children:** [`Components`](#Components) (unique, 0+), [`Container`](#Container) (0+), \
+                 [`Template`](#Template) (0+)\n"
+            ),
+            "{page}"
+        );
+        assert!(
+            page.contains("- **Children:** [`Container`](#Container) (1)\n"),
+            "{page}"
+        );
+        assert!(page.contains("[`Node`](index.md#Node)"), "{page}");
+    }
+
+    #[test]
+    fn docs_markdown_cells() {
+        let page = docs("docs_markdown_cells", "docs/definitions.tmd", "markdown", "index.md");
+        assert!(
+            page.contains("| `gap` | `f32` | `GAP` |  | Space between children, `4 \\| 8` or <GAP> by default. |\n"),
+            "{page}"
+        );
+        assert!(
+            page.contains("| `left` | `f32` | `0` |  | Space on the left, in pixels. |\n"),
+            "{page}"
+        );
+        assert!(
+            page.contains("| `align` | `Option<`[`base::Align`](base.md#Align)`>` |  | one of `Start`, `End` |  |\n"),
+            "{page}"
+        );
+        assert!(page.contains("### `group Content`\n\nWhat a panel holds.\n"), "{page}");
+    }
+
+    #[test]
+    fn docs_html() {
+        let page = docs("docs_html", "docs/definitions.tmd", "html", "index.html");
+        assert!(page.contains("<a href=\"base.html\">base</a>"), "{page}");
+        assert!(
+            page.contains("<dt>Attributes</dt><dd><code>Description(&quot;Panel&quot;)</code></dd>"),
+            "{page}"
+        );
+        assert!(
+            page.contains("<td>Space between children, `4 | 8` or &lt;GAP&gt; by default.</td>"),
+            "{page}"
+        );
+        assert!(
+            page.contains(
+                "<td><code>Option&lt;</code><a href=\"base.html#Align\"><code>base::Align</code></a><code>&gt;</code></td>"
+            ),
+            "{page}"
+        );
+        assert!(
+            page.contains("<dt>Children</dt><dd><a href=\"#Content\"><code>Content</code></a> (0+)</dd>"),
+            "{page}"
+        );
+
+        let page = docs("docs_html_patterns", "schema.tmd", "html", "index.html");
+        assert!(
+            page.contains("<tr><td><code>Px</code></td><td><code>f32</code></td><td><code>([0-9]+(?:\\.[0-9]+)?)px</code></td><td></td></tr>"),
+            "{page}"
+        );
+    }
+}
@@ -23,12 +23,16 @@ pub struct Directive {
 pub struct Annotation {
     pub name: String,
     pub value: Option<AnnotationValue>,
+    /// Byte offset of the annotation in the source file.
+    pub position: usize,
 }
 
 #[derive(Clone, Debug)]
 pub enum AnnotationValue {
     String(String),
     Array(Vec<String>),
+    /// The text between the parentheses of `@name(...)`.
+    Arguments(String),
 }
 
 #[derive(Debug)]
@@ -70,6 +74,8 @@ pub enum CustomType {
     Element(Element),
     Group(Group),
     Expression(Expression),
+    Alias(Alias),
+    Constant(Constant),
 }
 
 impl CustomType {
@@ -98,6 +104,16 @@ impl CustomType {
         matches!(self, CustomType::Expression(_))
     }
 
+    #[must_use]
+    pub const fn is_alias(&self) -> bool {
+        matches!(self, CustomType::Alias(_))
+    }
+
+    #[must_use]
+    pub const fn is_constant(&self) -> bool {
+        matches!(self, CustomType::Constant(_))
+    }
+
     #[must_use]
     pub const fn unwrap_struct(&self) -> &Struct {
         match self {
@@ -137,21 +153,82 @@ impl CustomType {
             _ => panic!("Not an expression"),
         }
     }
+
+    #[must_use]
+    pub const fn unwrap_alias(&self) -> &Alias {
+        match self {
+            CustomType::Alias(value) => value,
+            _ => panic!("Not an alias"),
+        }
+    }
+
+    #[must_use]
+    pub const fn unwrap_constant(&self) -> &Constant {
+        match self {
+            CustomType::Constant(value) => value,
+            _ => panic!("Not a constant"),
+        }
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            CustomType::Struct(value) => &value.name,
+            CustomType::Enum(value) => &value.name,
+            CustomType::Element(value) => &value.name,
+            CustomType::Group(value) => &value.name,
+            CustomType::Expression(value) => &value.name,
+            CustomType::Alias(value) => &value.name,
+            CustomType::Constant(value) => &value.name,
+        }
+    }
+
+    /// The `///` comment above the declaration.
+    #[must_use]
+    pub fn doc(&self) -> Option<&str> {
+        match self {
+            CustomType::Struct(value) => value.doc.as_deref(),
+            CustomType::Enum(value) => value.doc.as_deref(),
+            CustomType::Element(value) => value.doc.as_deref(),
+            CustomType::Group(value) => value.doc.as_deref(),
+            CustomType::Expression(value) => value.doc.as_deref(),
+            CustomType::Alias(value) => value.doc.as_deref(),
+            CustomType::Constant(value) => value.doc.as_deref(),
+        }
+    }
+
+    /// The fields of a struct, element or expression.
+    #[must_use]
+    pub fn fields(&self) -> &[Field] {
+        match self {
+            CustomType::Struct(value) => &value.fields,
+            CustomType::Element(value) => &value.fields,
+            CustomType::Expression(value) => &value.fields,
+            CustomType::Enum(_) | CustomType::Group(_) | CustomType::Alias(_) | CustomType::Constant(_) => &[],
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct Struct {
+    /// The `///` comment above the declaration.
+    pub doc: Option<String>,
     pub attributes: Vec<Attribute>,
     pub name: String,
-    pub generic: Option<String>,
+    pub generics: Vec<String>,
     pub fields: Vec<Field>,
+    /// Structs whose fields are inherited with `..Base`.
+    pub spreads: Vec<TypeRef>,
 }
 
 #[derive(Debug)]
 pub struct Field {
+    /// The `///` comment above the declaration.
+    pub doc: Option<String>,
     pub annotations: AnnotationList,
     pub name: String,
     pub ty: TypeRef,
+    pub default: Option<String>,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -187,29 +264,13 @@ impl TypeRef {
             }
         }
     }
-
-    #[must_use]
-    pub fn new_generic(path: &str, inner: &str) -> Self {
-        if let Some(pos) = path.rfind("::") {
-            let namespace = Some(path[..pos].to_string());
-            let ident = path[pos + 2..].to_string();
-            Self {
-                namespace,
-                ident: TypeIdent::Generic(ident, Box::new(TypeIdent::Simple(inner.to_string()))),
-            }
-        } else {
-            Self {
-                namespace: None,
-                ident: TypeIdent::Generic(path.to_string(), Box::new(TypeIdent::Simple(inner.to_string()))),
-            }
-        }
-    }
 }
 
 #[derive(Debug, Clone)]
 pub enum TypeIdent {
     Simple(String),
-    Generic(String, Box<TypeIdent>),
+    /// A generic type applied to its arguments, e.g. `Map<String, base::Item>`.
+    Generic(String, Vec<TypeRef>),
 }
 
 impl Default for TypeIdent {
@@ -222,20 +283,28 @@ impl Display for TypeIdent {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             TypeIdent::Simple(ident) => write!(f, "{ident}"),
-            TypeIdent::Generic(ident, inner) => write!(f, "{ident}_{inner}"),
+            TypeIdent::Generic(ident, arguments) => {
+                let arguments = arguments.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(f, "{ident}<{}>", arguments.join(", "))
+            }
         }
     }
 }
 
 #[derive(Debug)]
 pub struct Enum {
+    /// The `///` comment above the declaration.
+    pub doc: Option<String>,
     pub attributes: Vec<Attribute>,
     pub name: String,
+    pub generics: Vec<String>,
     pub variants: Vec<EnumVariant>,
 }
 
 #[derive(Debug)]
 pub struct EnumVariant {
+    /// The `///` comment above the declaration.
+    pub doc: Option<String>,
     pub annotations: AnnotationList,
     pub name: String,
     pub value: Option<TypeRef>,
@@ -243,17 +312,23 @@ pub struct EnumVariant {
 
 #[derive(Debug)]
 pub struct Element {
+    /// The `///` comment above the declaration.
+    pub doc: Option<String>,
     pub attributes: Vec<Attribute>,
     pub name: String,
     pub bind: TypeRef,
     pub fields: Vec<Field>,
+    pub spreads: Vec<TypeRef>,
 }
 
 #[derive(Debug)]
 pub struct Group {
+    /// The `///` comment above the declaration.
+    pub doc: Option<String>,
     pub annotations: AnnotationList,
     pub attributes: Vec<Attribute>,
     pub name: String,
+    pub ordered: bool,
     pub entries: Vec<GroupEntry>,
 }
 
@@ -262,6 +337,8 @@ pub struct GroupEntry {
     pub unique: bool,
     pub name: String,
     pub count: Option<Count>,
+    /// Alternatives of a `one_of(...)` entry, empty for a plain entry.
+    pub one_of: Vec<String>,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -295,7 +372,7 @@ impl Count {
             }
             Count::Range(min, max) => {
                 let min = value >= *min;
-                let max = value < *max;
+                let max = value <= *max;
                 if min && max {
                     CountEquality::Ok
                 } else if !min {
@@ -314,9 +391,9 @@ impl Count {
             }
             Count::OneOrMore => {
                 if value == 0 {
-                    CountEquality::Ok
-                } else {
                     CountEquality::Less
+                } else {
+                    CountEquality::Ok
                 }
             }
         }
@@ -339,10 +416,62 @@ impl Display for Count {
 
 #[derive(Debug)]
 pub struct Expression {
+    /// The `///` comment above the declaration.
+    pub doc: Option<String>,
     pub attributes: Vec<Attribute>,
     pub annotations: AnnotationList,
     pub name: String,
     pub fields: Vec<Field>,
+    pub spreads: Vec<TypeRef>,
+}
+
+/// `type Name = Target;`
+#[derive(Debug)]
+pub struct Alias {
+    /// The `///` comment above the declaration.
+    pub doc: Option<String>,
+    pub name: String,
+    pub ty: TypeRef,
+}
+
+/// `const NAME: Type = value;`
+#[derive(Debug)]
+pub struct Constant {
+    /// The `///` comment above the declaration.
+    pub doc: Option<String>,
+    pub name: String,
+    pub ty: TypeRef,
+    pub value: String,
+}
+
+/// Joins the `///` lines of a declaration, enum variant or field, without the slashes and the space that follows
+/// them: the lines right above it and the ones between its attributes or annotations and its name. A `///` line
+/// anywhere else is an ordinary comment.
+fn build_doc(node: &CstNode<RmlxNode>, source: &str) -> Option<String> {
+    fn doc_line(line: &str) -> Option<&str> {
+        let line = line.trim().strip_prefix("///")?;
+        (!line.starts_with('/')).then(|| line.strip_prefix(' ').unwrap_or(line))
+    }
+
+    let line_start = source[..node.start].rfind('\n').map_or(0, |i| i + 1);
+    let mut lines = if source[line_start..node.start].trim().is_empty() {
+        source[..line_start]
+            .lines()
+            .rev()
+            .map_while(doc_line)
+            .collect::<Vec<_>>()
+    } else {
+        vec![]
+    };
+    lines.reverse();
+
+    let name = node
+        .children
+        .iter()
+        .find(|c| c.kind == RmlxNode::Ident)
+        .map_or(node.start, |c| c.start);
+    lines.extend(source[node.start..name].lines().filter_map(doc_line));
+    (!lines.is_empty()).then(|| lines.join("\n"))
 }
 
 fn build_directive(node: &CstNode<RmlxNode>) -> Directive {
@@ -377,6 +506,10 @@ fn build_annotation_value(node: &CstNode<RmlxNode>) -> AnnotationValue {
                 .collect(),
         ),
         RmlxNode::String => AnnotationValue::String(trim_quotes(&child.text).to_string()),
+        RmlxNode::AnnotationArgs => {
+            let arguments = child.text.strip_prefix('(').and_then(|t| t.strip_suffix(')'));
+            AnnotationValue::Arguments(arguments.unwrap_or_default().trim().to_string())
+        }
         _ => unreachable!(),
     }
 }
@@ -393,7 +526,11 @@ fn build_annotation(node: &CstNode<RmlxNode>) -> Annotation {
         }
     }
 
-    Annotation { name, value }
+    Annotation {
+        name,
+        value,
+        position: node.start,
+    }
 }
 
 fn build_attributes(node: &CstNode<RmlxNode>) -> Vec<Attribute> {
@@ -431,57 +568,88 @@ fn build_attribute(node: &CstNode<RmlxNode>) -> Attribute {
     Attribute { name, value }
 }
 
-fn build_custom_type(node: &CstNode<RmlxNode>) -> CustomType {
+fn build_custom_type(node: &CstNode<RmlxNode>, source: &str) -> CustomType {
     assert!(!node.children.is_empty(), "CustomType node has no children");
 
     let first_child = &node.children[0];
     match first_child.kind {
-        RmlxNode::Struct => CustomType::Struct(build_struct(first_child)),
-        RmlxNode::Enum => CustomType::Enum(build_enum(first_child)),
-        RmlxNode::Element => CustomType::Element(build_element(first_child)),
-        RmlxNode::Group => CustomType::Group(build_group(first_child)),
-        RmlxNode::Expression => CustomType::Expression(build_expression(first_child)),
+        RmlxNode::Struct => CustomType::Struct(build_struct(first_child, source)),
+        RmlxNode::Enum => CustomType::Enum(build_enum(first_child, source)),
+        RmlxNode::Element => CustomType::Element(build_element(first_child, source)),
+        RmlxNode::Group => CustomType::Group(build_group(first_child, source)),
+        RmlxNode::Expression => CustomType::Expression(build_expression(first_child, source)),
+        RmlxNode::Alias => CustomType::Alias(build_alias(first_child, source)),
+        RmlxNode::Constant => CustomType::Constant(build_constant(first_child, source)),
         _ => panic!("Unexpected child kind in CustomType: {:?}", first_child.kind),
     }
 }
 
-fn build_struct(node: &CstNode<RmlxNode>) -> Struct {
+fn build_struct(node: &CstNode<RmlxNode>, source: &str) -> Struct {
     let mut attributes = Vec::new();
     let mut name = String::new();
-    let mut generic = None;
+    let mut generics = Vec::new();
     let mut fields = Vec::new();
+    let mut spreads = Vec::new();
 
     for child in &node.children {
         match child.kind {
             RmlxNode::AttributeList => attributes.extend(build_attributes(child)),
             RmlxNode::Ident => name.clone_from(&child.text),
-            RmlxNode::GenericType => {
-                let parts: Vec<&str> = child.text.split('<').collect();
-                if parts.len() == 2 {
-                    generic = Some(parts[1].trim_end_matches('>').to_string());
-                }
+            RmlxNode::GenericParams => generics = build_generic_params(child),
+            RmlxNode::Block => {
+                fields.extend(build_fields(child, source));
+                spreads.extend(build_spreads(child));
             }
-            RmlxNode::Block => fields.extend(build_fields(child)),
             _ => {}
         }
     }
 
     Struct {
+        doc: build_doc(node, source),
         attributes,
         name,
-        generic,
+        generics,
         fields,
+        spreads,
     }
 }
 
-fn build_fields(block_node: &CstNode<RmlxNode>) -> Vec<Field> {
+fn build_generic_params(node: &CstNode<RmlxNode>) -> Vec<String> {
+    node.children
+        .iter()
+        .filter(|child| child.kind == RmlxNode::Ident)
+        .map(|child| child.text.clone())
+        .collect()
+}
+
+fn build_type_ref(node: &CstNode<RmlxNode>) -> TypeRef {
+    if node.kind == RmlxNode::NsIdent {
+        return TypeRef::new(node.text.as_str());
+    }
+
+    let mut children = node
+        .children
+        .iter()
+        .filter(|child| matches!(child.kind, RmlxNode::NsIdent | RmlxNode::GenericType));
+    let base = TypeRef::new(children.next().expect("Unreachable!").text.as_str());
+    let TypeIdent::Simple(ident) = base.ident else {
+        unreachable!()
+    };
+
+    TypeRef {
+        namespace: base.namespace,
+        ident: TypeIdent::Generic(ident, children.map(build_type_ref).collect()),
+    }
+}
+
+fn build_fields(block_node: &CstNode<RmlxNode>, source: &str) -> Vec<Field> {
     let mut fields = Vec::new();
 
     for child in &block_node.children {
         if child.kind == RmlxNode::SimpleFields {
             for field_node in &child.children {
                 if field_node.kind == RmlxNode::SimpleField {
-                    fields.push(build_field(field_node));
+                    fields.push(build_field(field_node, source));
                 }
             }
         }
@@ -490,57 +658,75 @@ fn build_fields(block_node: &CstNode<RmlxNode>) -> Vec<Field> {
     fields
 }
 
-fn build_field(node: &CstNode<RmlxNode>) -> Field {
+/// Returns the types of the `..Base` entries of a block.
+fn build_spreads(block_node: &CstNode<RmlxNode>) -> Vec<TypeRef> {
+    block_node
+        .children
+        .iter()
+        .filter(|c| c.kind == RmlxNode::SimpleFields)
+        .flat_map(|c| &c.children)
+        .filter(|c| c.kind == RmlxNode::Spread)
+        .filter_map(|spread| {
+            spread
+                .children
+                .iter()
+                .find(|c| matches!(c.kind, RmlxNode::NsIdent | RmlxNode::GenericType))
+                .map(build_type_ref)
+        })
+        .collect()
+}
+
+fn build_field(node: &CstNode<RmlxNode>, source: &str) -> Field {
     let mut annotations = Vec::new();
     let mut name = String::new();
     let mut ty = TypeRef::default();
+    let mut default = None;
 
     for child in &node.children {
         match child.kind {
             RmlxNode::Annotation => annotations.push(build_annotation(child)),
             RmlxNode::Ident if name.is_empty() => name.clone_from(&child.text),
-            RmlxNode::NsIdent => ty = TypeRef::new(child.text.as_str()),
-            RmlxNode::GenericType => {
-                let parts: Vec<&str> = child.text.split('<').collect();
-                if parts.len() == 2 {
-                    ty = TypeRef::new_generic(parts[0], parts[1].trim_end_matches('>'));
-                } else {
-                    unimplemented!();
-                }
-            }
+            RmlxNode::DefaultValue => default = Some(child.text.clone()),
+            RmlxNode::NsIdent | RmlxNode::GenericType => ty = build_type_ref(child),
             _ => {}
         }
     }
 
     Field {
+        doc: build_doc(node, source),
         annotations: AnnotationList::new(annotations),
         name,
         ty,
+        default,
     }
 }
 
-fn build_enum(node: &CstNode<RmlxNode>) -> Enum {
+fn build_enum(node: &CstNode<RmlxNode>, source: &str) -> Enum {
     let mut attributes = Vec::new();
     let mut name = String::new();
+    let mut generics = Vec::new();
     let mut variants = Vec::new();
 
     for child in &node.children {
         match child.kind {
             RmlxNode::AttributeList => attributes.extend(build_attributes(child)),
             RmlxNode::Ident if name.is_empty() => name.clone_from(&child.text),
-            RmlxNode::EnumVariant => variants.push(build_enum_variant(child)),
+            RmlxNode::GenericParams => generics = build_generic_params(child),
+            RmlxNode::EnumVariant => variants.push(build_enum_variant(child, source)),
             _ => {}
         }
     }
 
     Enum {
+        doc: build_doc(node, source),
         attributes,
         name,
+        generics,
         variants,
     }
 }
 
-fn build_enum_variant(node: &CstNode<RmlxNode>) -> EnumVariant {
+fn build_enum_variant(node: &CstNode<RmlxNode>, source: &str) -> EnumVariant {
     let mut annotations = Vec::new();
     let mut name = String::new();
     let mut value = None;
@@ -549,62 +735,54 @@ fn build_enum_variant(node: &CstNode<RmlxNode>) -> EnumVariant {
         match child.kind {
             RmlxNode::Annotation => annotations.push(build_annotation(child)),
             RmlxNode::Ident => name.clone_from(&child.text),
-            RmlxNode::NsIdent => value = Some(TypeRef::new(child.text.as_str())),
-            RmlxNode::GenericType => {
-                let parts: Vec<&str> = child.text.split('<').collect();
-                if parts.len() == 2 {
-                    value = Some(TypeRef::new_generic(parts[0], parts[1].trim_end_matches('>')));
-                } else {
-                    unimplemented!();
-                }
-            }
+            RmlxNode::NsIdent | RmlxNode::GenericType => value = Some(build_type_ref(child)),
             _ => {}
         }
     }
 
     EnumVariant {
+        doc: build_doc(node, source),
         annotations: AnnotationList::new(annotations),
         name,
         value,
     }
 }
 
-fn build_element(node: &CstNode<RmlxNode>) -> Element {
+fn build_element(node: &CstNode<RmlxNode>, source: &str) -> Element {
     let mut attributes = Vec::new();
     let mut name = String::new();
     let mut bind = TypeRef::default();
     let mut fields = Vec::new();
+    let mut spreads = Vec::new();
 
     for child in &node.children {
         match child.kind {
             RmlxNode::AttributeList => attributes.extend(build_attributes(child)),
             RmlxNode::Ident => name.clone_from(&child.text),
-            RmlxNode::NsIdent => bind = TypeRef::new(child.text.as_str()),
-            RmlxNode::GenericType => {
-                let parts: Vec<&str> = child.text.split('<').collect();
-                if parts.len() == 2 {
-                    bind = TypeRef::new_generic(parts[0], parts[1].trim_end_matches('>'));
-                } else {
-                    unimplemented!();
-                }
+            RmlxNode::NsIdent | RmlxNode::GenericType => bind = build_type_ref(child),
+            RmlxNode::Block => {
+                fields.extend(build_fields(child, source));
+                spreads.extend(build_spreads(child));
             }
-            RmlxNode::Block => fields.extend(build_fields(child)),
             _ => {}
         }
     }
 
     Element {
+        doc: build_doc(node, source),
         attributes,
         name,
         bind,
         fields,
+        spreads,
     }
 }
 
-fn build_group(node: &CstNode<RmlxNode>) -> Group {
+fn build_group(node: &CstNode<RmlxNode>, source: &str) -> Group {
     let mut annotations = Vec::new();
     let mut attributes = Vec::new();
     let mut name = String::new();
+    let mut ordered = false;
     let mut entries = Vec::new();
 
     for child in &node.children {
@@ -612,15 +790,18 @@ fn build_group(node: &CstNode<RmlxNode>) -> Group {
             RmlxNode::Annotation => annotations.push(build_annotation(child)),
             RmlxNode::AttributeList => attributes.extend(build_attributes(child)),
             RmlxNode::Ident => name.clone_from(&child.text),
+            RmlxNode::Keyword if child.text == "ordered" => ordered = true,
             RmlxNode::GroupContent => entries.extend(build_group_entries(child)),
             _ => {}
         }
     }
 
     Group {
+        doc: build_doc(node, source),
         annotations: AnnotationList { inner: annotations },
         attributes,
         name,
+        ordered,
         entries,
     }
 }
@@ -641,20 +822,29 @@ fn build_group_entry(node: &CstNode<RmlxNode>) -> GroupEntry {
     let mut unique = false;
     let mut name = String::new();
     let mut count = None;
+    let mut one_of = Vec::new();
+    let mut choice = false;
 
     let mut iter = node.children.iter();
     consume_token(&mut iter, RmlxNode::Plus, Some("+"));
 
     for child in iter {
         match child.kind {
+            RmlxNode::NsIdent | RmlxNode::Ident if choice => one_of.push(child.text.clone()),
             RmlxNode::NsIdent | RmlxNode::Ident => name.clone_from(&child.text),
             RmlxNode::Keyword if child.text == "unique" => unique = true,
+            RmlxNode::Keyword if child.text == "one_of" => choice = true,
             RmlxNode::Count => count = Some(build_count(child)),
             _ => {}
         }
     }
 
-    GroupEntry { unique, name, count }
+    GroupEntry {
+        unique,
+        name,
+        count,
+        one_of,
+    }
 }
 
 fn build_count(node: &CstNode<RmlxNode>) -> Count {
@@ -681,37 +871,74 @@ fn build_count(node: &CstNode<RmlxNode>) -> Count {
     }
 }
 
-fn build_expression(node: &CstNode<RmlxNode>) -> Expression {
+fn build_expression(node: &CstNode<RmlxNode>, source: &str) -> Expression {
     let mut attributes = Vec::new();
     let mut annotations = Vec::new();
     let mut name = String::new();
     let mut fields = Vec::new();
+    let mut spreads = Vec::new();
 
     for child in &node.children {
         match child.kind {
             RmlxNode::AttributeList => attributes.extend(build_attributes(child)),
             RmlxNode::Annotation => annotations.push(build_annotation(child)),
             RmlxNode::Ident => name.clone_from(&child.text),
-            RmlxNode::Block => fields.extend(
-                child
-                    .children
-                    .iter()
-                    .find(|c| matches!(c.kind, RmlxNode::SimpleFields))
-                    .unwrap()
-                    .children
-                    .iter()
-                    .filter(|c| matches!(c.kind, RmlxNode::SimpleField))
-                    .map(build_field),
-            ),
+            RmlxNode::Block => {
+                fields.extend(build_fields(child, source));
+                spreads.extend(build_spreads(child));
+            }
             _ => {}
         }
     }
 
     Expression {
+        doc: build_doc(node, source),
         attributes,
         annotations: AnnotationList::new(annotations),
         name,
         fields,
+        spreads,
+    }
+}
+
+fn build_alias(node: &CstNode<RmlxNode>, source: &str) -> Alias {
+    let mut name = String::new();
+    let mut ty = TypeRef::default();
+
+    for child in &node.children {
+        match child.kind {
+            RmlxNode::Ident => name.clone_from(&child.text),
+            RmlxNode::NsIdent | RmlxNode::GenericType => ty = build_type_ref(child),
+            _ => {}
+        }
+    }
+
+    Alias {
+        doc: build_doc(node, source),
+        name,
+        ty,
+    }
+}
+
+fn build_constant(node: &CstNode<RmlxNode>, source: &str) -> Constant {
+    let mut name = String::new();
+    let mut ty = TypeRef::default();
+    let mut value = String::new();
+
+    for child in &node.children {
+        match child.kind {
+            RmlxNode::Ident => name.clone_from(&child.text),
+            RmlxNode::NsIdent | RmlxNode::GenericType => ty = build_type_ref(child),
+            RmlxNode::DefaultValue => value.clone_from(&child.text),
+            _ => {}
+        }
+    }
+
+    Constant {
+        doc: build_doc(node, source),
+        name,
+        ty,
+        value,
     }
 }
 
@@ -734,14 +961,15 @@ where
 }
 
 #[must_use]
-pub fn build_schema_ast(cst: &CstNode<RmlxNode>) -> SchemaAst {
+/// Builds the AST of a schema from its CST and the source the CST was parsed from.
+pub fn build_schema_ast(cst: &CstNode<RmlxNode>, source: &str) -> SchemaAst {
     let mut directives = Vec::new();
     let mut custom_types = Vec::new();
 
     for child in &cst.children {
         match child.kind {
             RmlxNode::Directive => directives.push(build_directive(child)),
-            RmlxNode::CustomType => custom_types.push(build_custom_type(child)),
+            RmlxNode::CustomType => custom_types.push(build_custom_type(child, source)),
             _ => {}
         }
     }
@@ -30,6 +30,7 @@ pub enum RmlxNode {
 
     Annotation,
     AnnotationValue,
+    AnnotationArgs,
 
     Attribute,
     AttributeList,
@@ -39,6 +40,8 @@ pub enum RmlxNode {
 
     SimpleField,
     SimpleFields,
+    Spread,
+    DefaultValue,
     Block,
 
     GroupContent,
@@ -49,7 +52,10 @@ pub enum RmlxNode {
     Struct,
     Element,
     Expression,
+    Alias,
+    Constant,
     GenericType,
+    GenericParams,
     NsIdent,
     Count,
     CustomType,
@@ -67,9 +73,16 @@ impl CstKind for RmlxNode {
             Rule::base_types => RmlxNode::BaseType,
             Rule::directive => RmlxNode::Directive,
 
-            Rule::UNIQUE | Rule::STRUCT | Rule::ENUM | Rule::ELEMENT | Rule::EXPRESSION | Rule::GROUP => {
-                RmlxNode::Keyword
-            }
+            Rule::UNIQUE
+            | Rule::ORDERED
+            | Rule::ONE_OF
+            | Rule::STRUCT
+            | Rule::ENUM
+            | Rule::ELEMENT
+            | Rule::EXPRESSION
+            | Rule::TYPE
+            | Rule::CONST
+            | Rule::GROUP => RmlxNode::Keyword,
 
             Rule::COMMENT_MULTI | Rule::COMMENT_LINE => RmlxNode::Comment,
             Rule::WHITESPACE => RmlxNode::Whitespace,
@@ -81,14 +94,19 @@ impl CstKind for RmlxNode {
             Rule::block => RmlxNode::Block,
             Rule::simple_field => RmlxNode::SimpleField,
             Rule::simple_fields => RmlxNode::SimpleFields,
+            Rule::spread => RmlxNode::Spread,
+            Rule::default_value => RmlxNode::DefaultValue,
             Rule::annotation => RmlxNode::Annotation,
             Rule::attribute => RmlxNode::Attribute,
             Rule::attribute_list => RmlxNode::AttributeList,
             Rule::generic_type => RmlxNode::GenericType,
+            Rule::generic_params => RmlxNode::GenericParams,
             Rule::ns_ident => RmlxNode::NsIdent,
             Rule::group_content => RmlxNode::GroupContent,
             Rule::group => RmlxNode::Group,
             Rule::expression => RmlxNode::Expression,
+            Rule::alias => RmlxNode::Alias,
+            Rule::constant => RmlxNode::Constant,
             Rule::count => RmlxNode::Count,
             Rule::group_entry => RmlxNode::GroupEntry,
             Rule::custom_types => RmlxNode::CustomType,
@@ -104,6 +122,7 @@ impl CstKind for RmlxNode {
             Rule::AT => RmlxNode::AT,
             Rule::directive_content => RmlxNode::DirectiveContent,
             Rule::annotation_value => RmlxNode::AnnotationValue,
+            Rule::annotation_args => RmlxNode::AnnotationArgs,
             Rule::enum_variant => RmlxNode::EnumVariant,
             _ => RmlxNode::Symbol,
         }
@@ -0,0 +1,78 @@
+use crate::ast::{CustomType, SchemaAst, build_schema_ast};
+use crate::{AnalysisWorkspace, RmlxNode, RmlxParser, Rule, Symbol};
+use lexer_core::CstNode;
+use url::Url;
+
+fn contains(node: &CstNode<RmlxNode>, offset: usize) -> bool {
+    node.start <= offset && offset < node.end
+}
+
+/// Returns the `///` comment of what the identifier at byte `offset` of a schema names: a declaration, an enum
+/// variant or field of the declaration around it, or a type it refers to. Types declared in other schemas are
+/// looked up in the model loaded from `path`.
+#[must_use]
+pub fn doc_at(source: &str, path: &Url, offset: usize) -> Option<String> {
+    let cst = CstNode::new::<RmlxParser>(source, Rule::file).ok()?;
+    let ast = build_schema_ast(&cst, source);
+    let (index, node) = cst
+        .children
+        .iter()
+        .filter(|c| c.kind == RmlxNode::CustomType)
+        .enumerate()
+        .find(|(_, c)| contains(c, offset))?;
+    let declaration = &ast.custom_types[index];
+    let node = node.children.first()?;
+
+    let mut parent = node;
+    let ident = loop {
+        let child = parent.children.iter().find(|c| contains(c, offset))?;
+        if child.kind == RmlxNode::Ident {
+            break child;
+        }
+        parent = child;
+    };
+
+    match parent.kind {
+        _ if std::ptr::eq(parent, node) => declaration.doc().map(str::to_string),
+        RmlxNode::SimpleField => declaration.fields().iter().find(|f| f.name == ident.text)?.doc.clone(),
+        RmlxNode::EnumVariant => {
+            let CustomType::Enum(value) = declaration else {
+                return None;
+            };
+            value.variants.iter().find(|v| v.name == ident.text)?.doc.clone()
+        }
+        RmlxNode::NsIdent => type_doc(&ast, path, &parent.text.split_whitespace().collect::<String>()),
+        _ => None,
+    }
+}
+
+/// Returns the `///` comment of the type at `type_path` as written in the schema. A type without a namespace is
+/// looked up in the schema's namespace, then in the global one.
+fn type_doc(ast: &SchemaAst, path: &Url, type_path: &str) -> Option<String> {
+    let (namespace, name) = match type_path.rsplit_once("::") {
+        Some((namespace, name)) => (Some(namespace), name),
+        None => (None, type_path),
+    };
+    let own = ast
+        .directives
+        .iter()
+        .find(|d| d.name == "namespace")
+        .and_then(|d| d.value.as_deref());
+
+    if (namespace.is_none() || namespace == own)
+        && let Some(declaration) = ast.custom_types.iter().find(|ty| ty.name() == name)
+    {
+        return declaration.doc().map(str::to_string);
+    }
+
+    let model = AnalysisWorkspace::new(path.clone()).run().ok()?;
+    let namespaces = match namespace {
+        Some(namespace) => vec![Some(namespace)],
+        None => vec![own, None],
+    };
+    namespaces.into_iter().find_map(|namespace| {
+        let id = model.try_get_namespace_id(namespace)?;
+        let symbol = model.get_type_by_name(id, name).unwrap()?;
+        Some(symbol.doc().map(str::to_string))
+    })?
+}
@@ -5,10 +5,12 @@
 
 mod ast;
 mod cst;
+mod doc;
 mod semantic;
 
-pub use ast::{Count, CountEquality, SchemaAst, build_schema_ast};
+pub use ast::{BaseType, Count, CountEquality, SchemaAst, build_schema_ast};
 pub use cst::RmlxNode;
+pub use doc::doc_at;
 use lexer_core::CstNode;
 pub use pest::*;
 use pest_derive::Parser;
@@ -22,7 +24,7 @@ pub struct RmlxParser;
 impl RmlxParser {
     pub fn build_ast(content: &str) -> Result<SchemaAst, Error> {
         let cst = CstNode::new::<RmlxParser>(content, Rule::file).map_err(Error::PestError)?;
-        Ok(build_schema_ast(&cst))
+        Ok(build_schema_ast(&cst, content))
     }
 }
 
@@ -34,8 +36,13 @@ pub enum Error {
     #[error("Namespace {0} does not exist")]
     NamespaceNotFound(String),
 
-    #[error("{0}")]
-    IncorrectPattern(#[from] regex::Error),
+    #[error("{path}:{line}:{column}: Incorrect pattern: {source}")]
+    IncorrectPattern {
+        path: String,
+        line: usize,
+        column: usize,
+        source: regex::Error,
+    },
 
     #[error("{0}")]
     LoadError(#[from] LoadError),
@@ -49,9 +56,58 @@ pub enum Error {
     #[error("Element {0} not found")]
     ElementNotFound(String),
 
+    #[error("Root element not found")]
+    RootElementNotFound,
+
+    #[error("Layout doesn't use any definitions")]
+    DefinitionsNotFound,
+
+    #[error("Element {element} is not allowed in {group} group, allowed groups: {}", .allowed.join(", "))]
+    ElementNotAllowedInGroup {
+        element: String,
+        group: String,
+        allowed: Vec<String>,
+    },
+
+    #[error("Element {element} from {group} group can't follow elements from {after} group")]
+    ElementOutOfOrder {
+        element: String,
+        group: String,
+        after: String,
+    },
+
+    #[error("Expected exactly one element from one_of({}), found {actual}", .alternatives.join(", "))]
+    ChoiceNotSatisfied { alternatives: Vec<String>, actual: u32 },
+
+    #[error("Closing tag </{found}> doesn't match <{expected}>")]
+    MismatchedClosingTag { expected: String, found: String },
+
     #[error("Expression {0} not found")]
     ExpressionNotFound(String),
 
+    #[error("Struct {0} not found")]
+    StructNotFound(String),
+
+    #[error("Struct {name} implements {actual}, but {expected} is expected")]
+    StructImplTypeMismatch {
+        name: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Impl ${0} not found")]
+    ImplNotFound(String),
+
+    #[error("Impl ${name} is {actual}, but {expected} is expected")]
+    ImplKindMismatch {
+        name: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Impl ${0} is already defined")]
+    DuplicateImpl(String),
+
     #[error("Field {0} not found")]
     FieldNotFound(String),
 
@@ -73,15 +129,69 @@ pub enum Error {
     #[error("Expression {0} is not allowed in {1} group")]
     ExpressionIsNotAllowedInGroup(String, String), //Expression, Group
 
+    #[error("Expression {expression} can only be used inside {}", .groups.join(" or "))]
+    ExpressionOutsideRestrictedGroup { expression: String, groups: Vec<String> },
+
+    #[error("Invalid value of @{annotation} annotation: \"{value}\"")]
+    InvalidAnnotationValue { annotation: String, value: String },
+
+    #[error("@{annotation} can't be applied to {field} of type {ty}")]
+    MisplacedAnnotation {
+        annotation: String,
+        field: String,
+        ty: String,
+    },
+
+    #[error("Value {value} doesn't satisfy @{constraint}")]
+    ConstraintViolation { constraint: String, value: String },
+
+    #[error("Expected a list of {expected} elements, found {actual}")]
+    InvalidLength { expected: Length, actual: usize },
+
+    #[error("Invalid value of constant {constant}: {source}")]
+    InvalidConstant { constant: String, source: Box<Error> },
+
+    #[error("Constant {constant} is {actual}, but {expected} is expected")]
+    ConstantTypeMismatch {
+        constant: String,
+        expected: String,
+        actual: String,
+    },
+
     #[error("{0}::{1} is already defined")]
     AlreadyDefinedType(String, String),
 
+    #[error("{0} can't be spread, only struct fields can be inherited")]
+    InvalidSpread(String),
+
+    #[error("Field {field} is inherited from both {first} and {second}")]
+    ConflictingField {
+        field: String,
+        first: String,
+        second: String,
+    },
+
+    #[error("Inheritance cycle: {0}")]
+    InheritanceCycle(String),
+
     #[error("Duplicate field: {0}")]
     DuplicateField(String),
 
+    #[error("Duplicate key: {0}")]
+    DuplicateKey(String),
+
+    #[error("Map key must be String or an enum, found {key} in {map}")]
+    InvalidMapKey { map: String, key: String },
+
     #[error("Missing required field: {0}")]
     MissingRequiredField(String),
 
+    #[error("Invalid default value of {field}: {source}")]
+    InvalidDefaultValue { field: String, source: Box<Error> },
+
+    #[error("A value of {0} can only be written in a layout")]
+    StructuredValue(String),
+
     #[error("Not enough elements from {group} group: {actual} found, need {expected}")]
     InsufficientElements {
         group: String,
@@ -0,0 +1,71 @@
+use crate::ast::Alias;
+use crate::semantic::symbol::{Symbol, SymbolRef};
+use crate::{AnalysisWorkspace, Error, SchemaModel, TypeResolver, UnresolvedType};
+
+/// Another name for a type, declared with `type Name = Target;`. Types that name the alias are resolved to the
+/// target, so the alias never appears as the type of a field.
+#[derive(Debug, Clone)]
+pub struct AliasSymbol {
+    identifier: String,
+    doc: Option<String>,
+    target: SymbolRef,
+}
+
+impl AliasSymbol {
+    #[must_use]
+    pub const fn target(&self) -> SymbolRef {
+        self.target
+    }
+}
+
+impl Symbol for AliasSymbol {
+    fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
+    fn can_parse(&self, value: &str, model: &SchemaModel) -> Result<(), Error> {
+        model.get_type_by_ref(self.target).as_ref().can_parse(value, model)
+    }
+}
+
+#[derive(Debug)]
+pub struct UnresolvedAliasSymbol {
+    identifier: String,
+    doc: Option<String>,
+    target: UnresolvedType,
+    resolved: Option<SymbolRef>,
+}
+
+impl UnresolvedAliasSymbol {
+    pub fn new(alias: &Alias) -> Self {
+        Self {
+            identifier: alias.name.clone(),
+            doc: alias.doc.clone(),
+            target: alias.ty.clone().into(),
+            resolved: None,
+        }
+    }
+
+    pub const fn identifier(&self) -> &str {
+        self.identifier.as_str()
+    }
+}
+
+impl TypeResolver<AliasSymbol> for UnresolvedAliasSymbol {
+    fn resolve(&mut self, workspace: &mut AnalysisWorkspace) -> Result<bool, Error> {
+        self.resolved = workspace.get_type(&self.target);
+        Ok(self.resolved.is_some())
+    }
+
+    fn as_resolved_type(&self) -> AliasSymbol {
+        AliasSymbol {
+            identifier: self.identifier.clone(),
+            doc: self.doc.clone(),
+            target: self.resolved.expect("Unreachable!"),
+        }
+    }
+}
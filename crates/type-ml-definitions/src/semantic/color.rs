@@ -0,0 +1,89 @@
+use std::fmt::{Display, Formatter};
+
+/// An sRGB color with an alpha channel, written as `#RRGGBB[AA]`, `rgb(r, g, b)`, `rgba(r, g, b, a)`,
+/// `hsl(h, s%, l%)` or `hsla(h, s%, l%, a)`. Channels are `0..=255`, the alpha of the functional forms is `0..=1`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(hex) = value.strip_prefix('#') {
+            return Self::parse_hex(hex);
+        }
+
+        let (function, arguments) = value.strip_suffix(')')?.split_once('(')?;
+        let arguments = arguments.split(',').map(str::trim).collect::<Vec<_>>();
+        match (function.trim(), arguments.as_slice()) {
+            ("rgb", [r, g, b]) => Some(Self::rgba(channel(r)?, channel(g)?, channel(b)?, 255)),
+            ("rgba", [r, g, b, a]) => Some(Self::rgba(channel(r)?, channel(g)?, channel(b)?, alpha(a)?)),
+            ("hsl", [h, s, l]) => Self::hsla(h, s, l, 255),
+            ("hsla", [h, s, l, a]) => Self::hsla(h, s, l, alpha(a)?),
+            _ => None,
+        }
+    }
+
+    const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    fn parse_hex(hex: &str) -> Option<Self> {
+        if !matches!(hex.len(), 6 | 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        let a = if hex.len() == 8 { byte(6)? } else { 255 };
+        Some(Self::rgba(byte(0)?, byte(2)?, byte(4)?, a))
+    }
+
+    fn hsla(h: &str, s: &str, l: &str, a: u8) -> Option<Self> {
+        let h = h.parse::<f32>().ok()?.rem_euclid(360.0) / 60.0;
+        let s = percent(s)?;
+        let l = percent(l)?;
+
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = l - chroma / 2.0;
+        let to_channel = |v: f32| ((v + m) * 255.0).round() as u8;
+        Some(Self::rgba(to_channel(r), to_channel(g), to_channel(b), a))
+    }
+}
+
+fn channel(value: &str) -> Option<u8> {
+    value.parse().ok()
+}
+
+fn alpha(value: &str) -> Option<u8> {
+    let alpha = value.parse::<f32>().ok()?;
+    (0.0..=1.0).contains(&alpha).then(|| (alpha * 255.0).round() as u8)
+}
+
+fn percent(value: &str) -> Option<f32> {
+    let percent = value.strip_suffix('%').unwrap_or(value).trim().parse::<f32>().ok()?;
+    (0.0..=100.0).contains(&percent).then_some(percent / 100.0)
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)?;
+        if self.a != 255 {
+            write!(f, "{:02x}", self.a)?;
+        }
+        Ok(())
+    }
+}
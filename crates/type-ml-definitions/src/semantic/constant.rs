@@ -0,0 +1,78 @@
+use crate::ast::Constant;
+use crate::semantic::symbol::{Symbol, SymbolRef};
+use crate::{AnalysisWorkspace, Error, TypeResolver, UnresolvedType};
+
+/// A named value, declared with `const NAME: Type = value;`. Layouts may write the name, optionally prefixed with
+/// its namespace, wherever a value of the type is expected. A constant takes precedence over an enum variant with
+/// the same name.
+#[derive(Debug, Clone)]
+pub struct ConstantSymbol {
+    identifier: String,
+    doc: Option<String>,
+    ty: SymbolRef,
+    value: String,
+}
+
+impl ConstantSymbol {
+    #[must_use]
+    pub const fn ty(&self) -> SymbolRef {
+        self.ty
+    }
+
+    /// The value as written in the schema.
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Symbol for ConstantSymbol {
+    fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+}
+
+#[derive(Debug)]
+pub struct UnresolvedConstantSymbol {
+    identifier: String,
+    doc: Option<String>,
+    ty: UnresolvedType,
+    value: String,
+    resolved: Option<SymbolRef>,
+}
+
+impl UnresolvedConstantSymbol {
+    pub fn new(constant: &Constant) -> Self {
+        Self {
+            identifier: constant.name.clone(),
+            doc: constant.doc.clone(),
+            ty: constant.ty.clone().into(),
+            value: constant.value.clone(),
+            resolved: None,
+        }
+    }
+
+    pub const fn identifier(&self) -> &str {
+        self.identifier.as_str()
+    }
+}
+
+impl TypeResolver<ConstantSymbol> for UnresolvedConstantSymbol {
+    fn resolve(&mut self, workspace: &mut AnalysisWorkspace) -> Result<bool, Error> {
+        self.resolved = workspace.get_type(&self.ty);
+        Ok(self.resolved.is_some())
+    }
+
+    fn as_resolved_type(&self) -> ConstantSymbol {
+        ConstantSymbol {
+            identifier: self.identifier.clone(),
+            doc: self.doc.clone(),
+            ty: self.resolved.expect("Unreachable!"),
+            value: self.value.clone(),
+        }
+    }
+}
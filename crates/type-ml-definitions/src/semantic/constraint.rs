@@ -0,0 +1,242 @@
+use crate::Error;
+use crate::ast::{AnnotationList, AnnotationValue};
+use crate::semantic::enumeration::Pattern;
+use crate::semantic::structure::split_fields;
+use std::fmt::{Display, Formatter};
+
+/// Returns the value of the annotation as written: the string, the array or the text between the parentheses.
+pub(crate) fn annotation_text(value: Option<AnnotationValue>) -> String {
+    match value {
+        Some(AnnotationValue::String(value) | AnnotationValue::Arguments(value)) => value,
+        Some(AnnotationValue::Array(values)) => format!("[{}]", values.join(", ")),
+        None => String::new(),
+    }
+}
+
+pub(crate) fn invalid_annotation(annotation: &str, value: String) -> Error {
+    Error::InvalidAnnotationValue {
+        annotation: annotation.to_string(),
+        value,
+    }
+}
+
+/// The number of list elements or string characters allowed by a `@len(min..max)` or `@len "min..max"` field
+/// annotation. Both bounds are inclusive and either may be omitted, e.g. `1..` or `..8`. A single number is an
+/// exact length.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Length {
+    min: usize,
+    max: Option<usize>,
+}
+
+impl Length {
+    pub(crate) fn from_annotations(annotations: &AnnotationList) -> Result<Option<Self>, Error> {
+        let Some(annotation) = annotations.try_take("len") else {
+            return Ok(None);
+        };
+
+        let value = annotation_text(annotation.value);
+        match Self::parse(&value) {
+            Some(length) => Ok(Some(length)),
+            None => Err(invalid_annotation("len", value)),
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        let bound = |bound: &str| bound.trim().parse::<usize>().ok();
+        let Some((min, max)) = value.split_once("..") else {
+            let exact = bound(value)?;
+            return Some(Self {
+                min: exact,
+                max: Some(exact),
+            });
+        };
+
+        let min = if min.trim().is_empty() { 0 } else { bound(min)? };
+        let max = if max.trim().is_empty() { None } else { Some(bound(max)?) };
+        if max.is_some_and(|max| max < min) {
+            return None;
+        }
+
+        Some(Self { min, max })
+    }
+
+    #[must_use]
+    pub const fn min(&self) -> usize {
+        self.min
+    }
+
+    #[must_use]
+    pub const fn max(&self) -> Option<usize> {
+        self.max
+    }
+
+    pub fn check(&self, actual: usize) -> Result<(), Error> {
+        if actual < self.min || self.max.is_some_and(|max| actual > max) {
+            return Err(Error::InvalidLength {
+                expected: *self,
+                actual,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Length {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.max {
+            Some(max) if max == self.min => write!(f, "{max}"),
+            Some(max) => write!(f, "{}..{max}", self.min),
+            None => write!(f, "{}..", self.min),
+        }
+    }
+}
+
+/// The inclusive bounds of a numeric value set by a `@range(min, max)` field annotation. Either bound may be
+/// omitted, e.g. `@range(0, )`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Range {
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl Range {
+    fn from_annotations(annotations: &AnnotationList) -> Result<Option<Self>, Error> {
+        let Some(annotation) = annotations.try_take("range") else {
+            return Ok(None);
+        };
+
+        let value = annotation_text(annotation.value);
+        match Self::parse(&value) {
+            Some(range) => Ok(Some(range)),
+            None => Err(invalid_annotation("range", value)),
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        let (min, max) = value.split_once(',')?;
+        let bound = |bound: &str| {
+            let bound = bound.trim();
+            if bound.is_empty() {
+                Some(None)
+            } else {
+                bound.parse::<f64>().ok().map(Some)
+            }
+        };
+
+        let (min, max) = (bound(min)?, bound(max)?);
+        if min.zip(max).is_some_and(|(min, max)| max < min) {
+            return None;
+        }
+
+        Some(Self { min, max })
+    }
+
+    #[must_use]
+    pub const fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    #[must_use]
+    pub const fn max(&self) -> Option<f64> {
+        self.max
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: f64) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let bound = |bound: Option<f64>| bound.map(|b| b.to_string()).unwrap_or_default();
+        write!(f, "{}, {}", bound(self.min), bound(self.max))
+    }
+}
+
+/// The `@len`, `@range` and `@pattern` annotations of a field, checked against every value written for it.
+#[derive(Debug, Clone, Default)]
+pub struct Constraints {
+    length: Option<Length>,
+    range: Option<Range>,
+    pattern: Option<Pattern>,
+}
+
+impl Constraints {
+    pub(crate) fn from_annotations(annotations: &AnnotationList) -> Result<Self, Error> {
+        let pattern = match annotations.try_take("pattern") {
+            Some(annotation) => {
+                let anchored = annotations.try_take("unanchored").is_none();
+                let value = annotation_text(annotation.value);
+                let pattern =
+                    Pattern::new(value.clone(), anchored).map_err(|_| invalid_annotation("pattern", value))?;
+                Some(pattern)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            length: Length::from_annotations(annotations)?,
+            range: Range::from_annotations(annotations)?,
+            pattern,
+        })
+    }
+
+    #[must_use]
+    pub const fn length(&self) -> Option<Length> {
+        self.length
+    }
+
+    #[must_use]
+    pub const fn range(&self) -> Option<Range> {
+        self.range
+    }
+
+    #[must_use]
+    pub const fn pattern(&self) -> Option<&Pattern> {
+        self.pattern.as_ref()
+    }
+
+    /// Checks a value that is already known to parse as the field type. The length of a list is the number of
+    /// elements, the length of a string is the number of characters between the quotes. The schema only allows a
+    /// constraint on the types it applies to, so the remaining values, such as `None`, pass it.
+    pub fn check(&self, value: &str) -> Result<(), Error> {
+        let value = value.trim();
+        let violation = |constraint: String| Error::ConstraintViolation {
+            constraint,
+            value: value.to_string(),
+        };
+
+        if let Some(list) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            if let Some(length) = self.length {
+                length.check(split_fields(list).len())?;
+            }
+            return Ok(());
+        }
+
+        if let Some(text) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+            if let Some(length) = self.length
+                && length.check(text.chars().count()).is_err()
+            {
+                return Err(violation(format!("len({length})")));
+            }
+
+            if let Some(pattern) = &self.pattern
+                && !pattern.is_match(text)
+            {
+                return Err(violation(format!("pattern \"{}\"", pattern.source())));
+            }
+        }
+
+        if let Some(range) = self.range
+            && let Ok(number) = value.parse::<f64>()
+            && !range.contains(number)
+        {
+            return Err(violation(format!("range({range})")));
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
+use crate::Constraints;
 use crate::ast::{BaseType, Element, Field};
+use crate::semantic::{spread::Spreads, structure};
 use crate::{
     AnalysisWorkspace, TypeResolver, UnresolvedType,
     semantic::symbol::{Symbol, SymbolRef},
@@ -8,6 +10,7 @@ use std::collections::HashMap;
 #[derive(Debug, Clone)]
 pub struct ElementSymbol {
     identifier: String,
+    doc: Option<String>,
     fields: Vec<ResolvedField>,
     bind: SymbolRef,
     metadata: HashMap<String, Option<BaseType>>,
@@ -17,64 +20,140 @@ impl ElementSymbol {
     pub fn group(&self) -> SymbolRef {
         self.bind
     }
+
+    #[must_use]
+    pub fn fields(&self) -> &[ResolvedField] {
+        &self.fields
+    }
+
+    #[must_use]
+    pub fn metadata(&self) -> &HashMap<String, Option<BaseType>> {
+        &self.metadata
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ResolvedField {
     identifier: String,
+    doc: Option<String>,
     ty: SymbolRef,
+    required: bool,
+    constraints: Constraints,
+    default: Option<String>,
 }
 
 impl ResolvedField {
+    #[must_use]
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    /// The `///` comment of the declaration.
+    #[must_use]
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     pub fn ty(&self) -> SymbolRef {
         self.ty
     }
+
+    #[must_use]
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    #[must_use]
+    pub fn constraints(&self) -> &Constraints {
+        &self.constraints
+    }
+
+    #[must_use]
+    pub fn default(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+}
+
+impl ResolvedField {
+    /// An attribute inherited from a struct field. It isn't `@required`.
+    fn inherited(field: &structure::ResolvedField) -> Self {
+        Self {
+            identifier: field.identifier().to_string(),
+            doc: field.doc().map(str::to_string),
+            ty: field.ty(),
+            required: false,
+            constraints: field.constraints().clone(),
+            default: field.default().map(str::to_string),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct UnresolvedElementField {
     identifier: String,
+    doc: Option<String>,
     ty: UnresolvedType,
+    required: bool,
+    constraints: Constraints,
+    default: Option<String>,
 }
 
 impl UnresolvedElementField {
-    pub fn new(f: &Field) -> UnresolvedElementField {
+    pub fn new(f: &Field) -> Result<UnresolvedElementField, crate::Error> {
         let identifier = f.name.to_string();
         let ty = f.ty.clone().into();
+        let required = f.annotations.try_take("required").is_some();
+        let constraints = Constraints::from_annotations(&f.annotations)?;
+        let default = f.default.clone();
         //TODO Annotations
-        UnresolvedElementField { identifier, ty }
+        Ok(UnresolvedElementField {
+            identifier,
+            doc: f.doc.clone(),
+            ty,
+            required,
+            constraints,
+            default,
+        })
     }
 }
 
 #[derive(Debug)]
 pub struct UnresolvedElementSymbol {
     identifier: String,
+    doc: Option<String>,
     pub bind: UnresolvedType,
     pub resolved_bind: Option<SymbolRef>,
     pub fields: Vec<UnresolvedElementField>,
+    pub spreads: Spreads,
     pub metadata: HashMap<String, Option<BaseType>>,
     pub resolved: Vec<ResolvedField>,
 }
 
 impl UnresolvedElementSymbol {
-    pub fn new(s: &Element) -> UnresolvedElementSymbol {
+    pub fn new(s: &Element) -> Result<UnresolvedElementSymbol, crate::Error> {
         let identifier = s.name.to_string();
         let bind = s.bind.clone().into();
-        let fields = s.fields.iter().map(UnresolvedElementField::new).collect::<Vec<_>>();
+        let fields = s
+            .fields
+            .iter()
+            .map(UnresolvedElementField::new)
+            .collect::<Result<Vec<_>, _>>()?;
         let mut metadata = HashMap::new();
 
         s.attributes.iter().for_each(|a| {
             metadata.insert(a.name.clone(), a.value.clone());
         });
 
-        UnresolvedElementSymbol {
+        Ok(UnresolvedElementSymbol {
             identifier,
+            doc: s.doc.clone(),
             bind,
             resolved_bind: None,
             fields,
+            spreads: Spreads::new(&s.spreads),
             metadata,
             resolved: vec![],
-        }
+        })
     }
 
     pub const fn identifier(&self) -> &str {
@@ -88,7 +167,11 @@ impl TypeResolver<ElementSymbol> for UnresolvedElementSymbol {
             if let Some(ty) = workspace.get_type(&f.ty) {
                 self.resolved.push(ResolvedField {
                     identifier: f.identifier.clone(),
+                    doc: f.doc.clone(),
                     ty,
+                    required: f.required,
+                    constraints: f.constraints.clone(),
+                    default: f.default.clone(),
                 });
                 return false;
             }
@@ -111,7 +194,15 @@ impl TypeResolver<ElementSymbol> for UnresolvedElementSymbol {
             }
         }
 
-        Ok(self.fields.is_empty() && self.resolved_bind.is_some())
+        if !self.spreads.resolve(workspace)? || !self.fields.is_empty() || self.resolved_bind.is_none() {
+            return Ok(false);
+        }
+
+        let declared = self.resolved.iter().map(|f| f.identifier.as_str()).collect::<Vec<_>>();
+        let inherited = self.spreads.take_inherited(&declared)?;
+        self.resolved
+            .splice(0..0, inherited.iter().map(ResolvedField::inherited));
+        Ok(true)
     }
 
     fn as_resolved_type(&self) -> ElementSymbol {
@@ -119,6 +210,7 @@ impl TypeResolver<ElementSymbol> for UnresolvedElementSymbol {
         assert!(self.resolved_bind.is_some());
         ElementSymbol {
             identifier: self.identifier.clone(),
+            doc: self.doc.clone(),
             fields: self.resolved.clone(),
             metadata: self.metadata.clone(),
             bind: self.resolved_bind.expect("Unreachable!"),
@@ -130,6 +222,10 @@ impl Symbol for ElementSymbol {
     fn identifier(&self) -> &str {
         &self.identifier
     }
+
+    fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
 }
 
 impl ElementSymbol {
@@ -1,15 +1,72 @@
 use crate::Error::InvalidArgumentType;
 use crate::ast::{AnnotationValue, BaseType, Enum};
+use crate::semantic::constraint::{annotation_text, invalid_annotation};
 use crate::{
     AnalysisWorkspace, Error, SchemaModel, TypeResolver, UnresolvedType,
-    semantic::symbol::{Symbol, TypeRef},
+    semantic::symbol::{GenericBase, GenericSymbol, Symbol, TypeRef},
 };
+use lexer_core::line_col;
 use regex::Regex;
 use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Matches `Variant(value)` for variants that carry a value.
+static VARIANT_WITH_VALUE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([a-zA-Z][a-zA-Z0-9_]*)\((.*)\)$").expect("Unreachable!"));
+
+/// A compiled `@pattern`. Unless the variant is marked `@unanchored`, the pattern must match the whole value.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    source: String,
+    regex: Regex,
+    anchored: bool,
+}
+
+impl Pattern {
+    pub(crate) fn new(source: String, anchored: bool) -> Result<Self, regex::Error> {
+        let regex = if anchored {
+            Regex::new(&format!("^(?:{source})$"))?
+        } else {
+            Regex::new(&source)?
+        };
+
+        Ok(Self {
+            source,
+            regex,
+            anchored,
+        })
+    }
+
+    /// The pattern as written in the schema.
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    #[must_use]
+    pub const fn is_anchored(&self) -> bool {
+        self.anchored
+    }
+
+    #[must_use]
+    pub fn is_match(&self, value: &str) -> bool {
+        self.regex.is_match(value)
+    }
+
+    /// Returns the text of the first capture group, or the whole match if the pattern has no groups.
+    #[must_use]
+    pub fn payload<'a>(&self, value: &'a str) -> Option<&'a str> {
+        let captures = self.regex.captures(value)?;
+        let payload = captures.get(1).or_else(|| captures.get(0)).expect("Unreachable!");
+        Some(payload.as_str())
+    }
+}
 
 #[derive(Debug)]
 pub struct UnresolvedEnumSymbol {
     identifier: String,
+    doc: Option<String>,
+    generics: Vec<String>,
     variants: Vec<UnresolvedVariant>,
     metadata: HashMap<String, Option<BaseType>>,
     resolved: Vec<EnumVariant>,
@@ -18,25 +75,38 @@ pub struct UnresolvedEnumSymbol {
 #[derive(Debug)]
 pub struct UnresolvedVariant {
     identifier: String,
+    doc: Option<String>,
     ty: Option<UnresolvedType>,
-    pattern: Option<String>,
+    pattern: Option<Pattern>,
 }
 
 impl UnresolvedEnumSymbol {
-    pub fn new(e: &Enum) -> Self {
+    /// Compiles the `@pattern` of every variant; `source_text` and `path` locate invalid patterns.
+    pub fn new(e: &Enum, source_text: &str, path: &str) -> Result<Self, Error> {
         let identifier = e.name.clone();
         let mut variants = vec![];
         let mut metadata = HashMap::new();
 
-        e.variants.iter().for_each(|v| {
+        e.variants.iter().try_for_each(|v| {
             let identifier = v.name.clone();
             let ty = v.value.as_ref().map(|ty| ty.clone().into());
+            let anchored = v.annotations.try_take("unanchored").is_none();
             let pattern = v.annotations.try_take("pattern");
             let pattern = if let Some(annotation) = pattern {
                 if let Some(value) = annotation.value {
                     match value {
-                        AnnotationValue::String(string) => Some(string),
-                        AnnotationValue::Array(_) => panic!("The value should be a string"),
+                        AnnotationValue::String(string) => Some(Pattern::new(string, anchored).map_err(|source| {
+                            let (line, column) = line_col(source_text, annotation.position);
+                            Error::IncorrectPattern {
+                                path: path.to_string(),
+                                line,
+                                column,
+                                source,
+                            }
+                        })?),
+                        value @ (AnnotationValue::Array(_) | AnnotationValue::Arguments(_)) => {
+                            return Err(invalid_annotation("pattern", annotation_text(Some(value))));
+                        }
                     }
                 } else {
                     None
@@ -48,31 +118,53 @@ impl UnresolvedEnumSymbol {
             //TODO Annotations
             variants.push(UnresolvedVariant {
                 identifier,
+                doc: v.doc.clone(),
                 ty,
                 pattern,
             });
-        });
+            Ok::<_, Error>(())
+        })?;
 
         e.attributes.iter().for_each(|a| {
             metadata.insert(a.name.to_string(), a.value.clone());
         });
 
-        Self {
+        Ok(Self {
             identifier,
+            doc: e.doc.clone(),
+            generics: e.generics.clone(),
             variants,
             metadata,
             resolved: vec![],
-        }
+        })
     }
 
     pub const fn identifier(&self) -> &str {
         self.identifier.as_str()
     }
+
+    pub const fn is_generic(&self) -> bool {
+        !self.generics.is_empty()
+    }
+
+    pub fn as_generic_symbol(&self) -> GenericSymbol {
+        assert!(self.variants.is_empty());
+        GenericSymbol::new(
+            self.identifier.clone(),
+            self.generics.clone(),
+            GenericBase::Enum {
+                doc: self.doc.clone(),
+                variants: self.resolved.clone(),
+                metadata: self.metadata.clone(),
+            },
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct EnumSymbol {
     pub identifier: String,
+    pub doc: Option<String>,
     pub variants: Vec<EnumVariant>,
     pub metadata: HashMap<String, Option<BaseType>>,
 }
@@ -80,18 +172,43 @@ pub struct EnumSymbol {
 #[derive(Debug, Clone)]
 pub struct EnumVariant {
     pub identifier: String,
+    pub doc: Option<String>,
     pub ty: Option<TypeRef>,
-    pub pattern: Option<String>,
+    pub pattern: Option<Pattern>,
+}
+
+impl EnumSymbol {
+    /// Finds the variant written as `value` and the text of its payload, if any.
+    #[must_use]
+    pub fn match_variant<'a>(&self, value: &'a str) -> Option<(&EnumVariant, Option<&'a str>)> {
+        self.variants.iter().find_map(|variant| {
+            if let Some(pattern) = &variant.pattern
+                && let Some(payload) = pattern.payload(value)
+            {
+                return Some((variant, variant.ty.as_ref().map(|_| payload)));
+            }
+
+            if variant.ty.is_some()
+                && let Some(cap) = VARIANT_WITH_VALUE.captures(value)
+                && variant.identifier == cap.get(1).expect("Unreachable!").as_str()
+            {
+                return Some((variant, Some(cap.get(2).expect("Unreachable!").as_str())));
+            }
+
+            (variant.identifier == value).then_some((variant, None))
+        })
+    }
 }
 
 impl TypeResolver<EnumSymbol> for UnresolvedEnumSymbol {
     fn resolve(&mut self, workspace: &mut AnalysisWorkspace) -> Result<bool, crate::Error> {
         self.variants.retain(|v| {
             if let Some(ty) = &v.ty {
-                if let Some(ty) = workspace.get_type(ty) {
+                if let Some(ty) = workspace.get_type_ref(ty, &self.generics) {
                     self.resolved.push(EnumVariant {
                         identifier: v.identifier.clone(),
-                        ty: Some(TypeRef::Concrete(ty)),
+                        doc: v.doc.clone(),
+                        ty: Some(ty),
                         pattern: v.pattern.clone(),
                     });
                     return false;
@@ -100,6 +217,7 @@ impl TypeResolver<EnumSymbol> for UnresolvedEnumSymbol {
             }
             self.resolved.push(EnumVariant {
                 identifier: v.identifier.clone(),
+                doc: v.doc.clone(),
                 ty: None,
                 pattern: v.pattern.clone(),
             });
@@ -113,6 +231,7 @@ impl TypeResolver<EnumSymbol> for UnresolvedEnumSymbol {
         assert!(self.variants.is_empty());
         EnumSymbol {
             identifier: self.identifier.clone(),
+            doc: self.doc.clone(),
             variants: self.resolved.clone(),
             metadata: self.metadata.clone(),
         }
@@ -124,37 +243,22 @@ impl Symbol for EnumSymbol {
         &self.identifier
     }
 
-    fn can_parse(&self, value: &str, model: &SchemaModel) -> Result<(), Error> {
-        let default_inner_regex = Regex::new(r"([a-zA-Z][a-zA-Z0-9_]*)\((.*)\)")?;
-
-        for variant in &self.variants {
-            let mut result = false;
-
-            if let Some(pattern) = &variant.pattern {
-                let regex = Regex::new(pattern)?;
-                result = regex.is_match(value);
-            }
-
-            if !result
-                && default_inner_regex.is_match(value)
-                && let Some(ty) = &variant.ty
-                && let Some(cap) = default_inner_regex.captures(value)
-            {
-                let value = cap.get(2).expect("Unreachable!").as_str();
-                let ty = model.get_type_by_ref(ty.as_concrete()).unwrap().expect("Unreachable!");
-                ty.can_parse(value, model)?;
-                result = variant.identifier == cap.get(1).expect("Unreachable!").as_str();
-            }
+    fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
 
-            if !result {
-                result = variant.identifier == value;
-            }
+    fn can_parse(&self, value: &str, model: &SchemaModel) -> Result<(), Error> {
+        let Some((variant, payload)) = self.match_variant(value) else {
+            return Err(InvalidArgumentType("Enum variant".to_string(), value.to_string()));
+        };
 
-            if result {
-                return Ok(());
-            }
+        if let Some(ty) = &variant.ty
+            && let Some(payload) = payload
+        {
+            let ty = model.get_type_by_ref(ty.as_concrete()).unwrap().expect("Unreachable!");
+            ty.can_parse(payload, model)?;
         }
 
-        Err(InvalidArgumentType("Enum variant".to_string(), value.to_string()))
+        Ok(())
     }
 }
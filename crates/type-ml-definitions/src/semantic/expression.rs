@@ -1,34 +1,46 @@
 use crate::ast::{Annotation, AnnotationValue, BaseType, Expression, Field};
-use crate::{AnalysisWorkspace, Symbol, SymbolRef, TypeResolver, UnresolvedType};
+use crate::semantic::constraint::{annotation_text, invalid_annotation};
+use crate::semantic::{spread::Spreads, structure::ResolvedField};
+use crate::{AnalysisWorkspace, Constraints, Symbol, SymbolRef, TypeResolver, UnresolvedType};
 use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct UnresolvedExpressionField {
     identifier: String,
+    doc: Option<String>,
     ty: UnresolvedType,
     optional: bool,
+    constraints: Constraints,
+    default: Option<String>,
 }
 
 impl UnresolvedExpressionField {
-    pub fn new(f: &Field) -> Self {
+    pub fn new(f: &Field) -> Result<Self, crate::Error> {
         let identifier = f.name.to_string();
         let ty = f.ty.clone().into();
         let optional = f.annotations.try_take("optional").is_some();
-        Self {
+        let constraints = Constraints::from_annotations(&f.annotations)?;
+        let default = f.default.clone();
+        // TODO Annotations
+        Ok(Self {
             identifier,
+            doc: f.doc.clone(),
             ty,
             optional,
-        }
-        // TODO Annotations
+            constraints,
+            default,
+        })
     }
 }
 
 #[derive(Debug)]
 pub struct UnresolvedExpressionSymbol {
     identifier: String,
+    doc: Option<String>,
     metadata: HashMap<String, Option<BaseType>>,
 
     fields: Vec<UnresolvedExpressionField>,
+    spreads: Spreads,
     resolved_fields: Vec<ExpressionField>,
 
     groups: Vec<UnresolvedType>,
@@ -38,51 +50,59 @@ pub struct UnresolvedExpressionSymbol {
     resolved_restrict: Vec<SymbolRef>,
 }
 
-fn try_take_annotation(annotation: Option<Annotation>) -> Vec<UnresolvedType> {
+fn try_take_annotation(annotation: Option<Annotation>) -> Result<Vec<UnresolvedType>, crate::Error> {
     if let Some(annotation) = annotation
         && let Some(value) = annotation.value
     {
         match value {
-            AnnotationValue::Array(array) => array
+            AnnotationValue::Array(array) => Ok(array
                 .into_iter()
                 .map(|group| {
                     UnresolvedType {
-                        generic_base: None,
                         namespace: None, //TODO namespace
                         identifier: group,
+                        arguments: vec![],
                     }
                 })
-                .collect(),
-            AnnotationValue::String(_) => panic!("Value should be an array"),
+                .collect()),
+            value @ (AnnotationValue::String(_) | AnnotationValue::Arguments(_)) => {
+                Err(invalid_annotation(&annotation.name, annotation_text(Some(value))))
+            }
         }
     } else {
-        vec![]
+        Ok(vec![])
     }
 }
 
 impl UnresolvedExpressionSymbol {
-    pub fn new(e: &Expression) -> Self {
+    pub fn new(e: &Expression) -> Result<Self, crate::Error> {
         let identifier = e.name.to_string();
-        let fields = e.fields.iter().map(UnresolvedExpressionField::new).collect::<Vec<_>>();
+        let fields = e
+            .fields
+            .iter()
+            .map(UnresolvedExpressionField::new)
+            .collect::<Result<Vec<_>, _>>()?;
         let mut metadata = HashMap::new();
 
         e.attributes.iter().for_each(|a| {
             metadata.insert(a.name.clone(), a.value.clone());
         });
 
-        let groups = try_take_annotation(e.annotations.try_take("groups"));
-        let restrict = try_take_annotation(e.annotations.try_take("restrict"));
+        let groups = try_take_annotation(e.annotations.try_take("groups"))?;
+        let restrict = try_take_annotation(e.annotations.try_take("restrict"))?;
 
-        Self {
+        Ok(Self {
             identifier,
+            doc: e.doc.clone(),
             metadata,
             fields,
+            spreads: Spreads::new(&e.spreads),
             groups,
             restrict,
             resolved_restrict: vec![],
             resolved_groups: vec![],
             resolved_fields: vec![],
-        }
+        })
     }
 
     pub const fn identifier(&self) -> &str {
@@ -96,8 +116,11 @@ impl TypeResolver<ExpressionSymbol> for UnresolvedExpressionSymbol {
             if let Some(ty) = workspace.get_type(&f.ty) {
                 self.resolved_fields.push(ExpressionField {
                     identifier: f.identifier.clone(),
+                    doc: f.doc.clone(),
                     ty,
                     optional: f.optional,
+                    constraints: f.constraints.clone(),
+                    default: f.default.clone(),
                 });
                 return false;
             }
@@ -120,7 +143,23 @@ impl TypeResolver<ExpressionSymbol> for UnresolvedExpressionSymbol {
             true
         });
 
-        Ok(self.fields.is_empty() && self.groups.is_empty() && self.restrict.is_empty())
+        if !self.spreads.resolve(workspace)?
+            || !self.fields.is_empty()
+            || !self.groups.is_empty()
+            || !self.restrict.is_empty()
+        {
+            return Ok(false);
+        }
+
+        let declared = self
+            .resolved_fields
+            .iter()
+            .map(|f| f.identifier.as_str())
+            .collect::<Vec<_>>();
+        let inherited = self.spreads.take_inherited(&declared)?;
+        self.resolved_fields
+            .splice(0..0, inherited.iter().map(ExpressionField::inherited));
+        Ok(true)
     }
 
     fn as_resolved_type(&self) -> ExpressionSymbol {
@@ -129,6 +168,7 @@ impl TypeResolver<ExpressionSymbol> for UnresolvedExpressionSymbol {
         assert!(self.restrict.is_empty());
         ExpressionSymbol {
             identifier: self.identifier.clone(),
+            doc: self.doc.clone(),
             metadata: self.metadata.clone(),
             fields: self.resolved_fields.clone(),
             groups: self.resolved_groups.clone(),
@@ -140,16 +180,37 @@ impl TypeResolver<ExpressionSymbol> for UnresolvedExpressionSymbol {
 #[derive(Debug, Clone)]
 pub struct ExpressionField {
     identifier: String,
+    doc: Option<String>,
     ty: SymbolRef,
     optional: bool,
+    constraints: Constraints,
+    default: Option<String>,
 }
 
 impl ExpressionField {
+    /// An argument inherited from a struct field. It is required unless the field has a default.
+    fn inherited(field: &ResolvedField) -> Self {
+        Self {
+            identifier: field.identifier().to_string(),
+            doc: field.doc().map(str::to_string),
+            ty: field.ty(),
+            optional: false,
+            constraints: field.constraints().clone(),
+            default: field.default().map(str::to_string),
+        }
+    }
+
     #[must_use]
     pub fn identifier(&self) -> &str {
         &self.identifier
     }
 
+    /// The `///` comment of the declaration.
+    #[must_use]
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     #[must_use]
     pub fn ty(&self) -> SymbolRef {
         self.ty
@@ -159,11 +220,22 @@ impl ExpressionField {
     pub fn is_optional(&self) -> bool {
         self.optional
     }
+
+    #[must_use]
+    pub fn constraints(&self) -> &Constraints {
+        &self.constraints
+    }
+
+    #[must_use]
+    pub fn default(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ExpressionSymbol {
     identifier: String,
+    doc: Option<String>,
     metadata: HashMap<String, Option<BaseType>>,
     fields: Vec<ExpressionField>,
     groups: Vec<SymbolRef>,
@@ -190,10 +262,19 @@ impl ExpressionSymbol {
     pub fn restrict(&self) -> &[SymbolRef] {
         &self.restrict
     }
+
+    #[must_use]
+    pub fn metadata(&self) -> &HashMap<String, Option<BaseType>> {
+        &self.metadata
+    }
 }
 
 impl Symbol for ExpressionSymbol {
     fn identifier(&self) -> &str {
         &self.identifier
     }
+
+    fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
 }
@@ -11,8 +11,11 @@ use std::collections::{HashMap, HashSet};
 #[derive(Debug, Clone)]
 pub struct GroupSymbol {
     identifier: String,
+    doc: Option<String>,
     extend: bool,
+    ordered: bool,
     groups: Vec<GroupConfig>,
+    metadata: HashMap<String, Option<BaseType>>,
 }
 
 impl GroupSymbol {
@@ -26,26 +29,41 @@ impl GroupSymbol {
         self.extend
     }
 
+    /// Children of an ordered group must follow the order of its entries.
+    #[must_use]
+    pub fn ordered(&self) -> bool {
+        self.ordered
+    }
+
+    #[must_use]
+    pub fn metadata(&self) -> &HashMap<String, Option<BaseType>> {
+        &self.metadata
+    }
+
     #[must_use]
     pub fn main(root: SymbolRef) -> Self {
         Self {
             identifier: String::from("Main"),
+            doc: None,
             extend: false,
+            ordered: false,
             groups: vec![GroupConfig {
                 symbol: root,
                 unique: true,
                 count: Some(Count::Single(1)),
+                choice: None,
             }],
+            metadata: HashMap::default(),
         }
     }
 
+    /// The allowed number of children from each entry, in declaration order.
     #[must_use]
-    pub fn get_constraints(&self) -> HashMap<SymbolRef, Count> {
-        let mut map = HashMap::new();
-        self.groups.iter().for_each(|g| {
-            map.insert(g.symbol, g.count.unwrap_or(Count::ZeroOrMore)); //TODO set this value by default
-        });
-        map
+    pub fn get_constraints(&self) -> Vec<(SymbolRef, Count)> {
+        self.groups
+            .iter()
+            .map(|g| (g.symbol, g.count.unwrap_or(Count::ZeroOrMore))) //TODO set this value by default
+            .collect()
     }
 
     #[must_use]
@@ -56,6 +74,21 @@ impl GroupSymbol {
         });
         set
     }
+
+    /// The alternatives of each `one_of(...)` entry.
+    #[must_use]
+    pub fn get_choices(&self) -> Vec<Vec<SymbolRef>> {
+        let mut choices: Vec<(usize, Vec<SymbolRef>)> = Vec::new();
+        self.groups.iter().for_each(|g| {
+            if let Some(choice) = g.choice {
+                match choices.iter_mut().find(|(id, _)| *id == choice) {
+                    Some((_, alternatives)) => alternatives.push(g.symbol),
+                    None => choices.push((choice, vec![g.symbol])),
+                }
+            }
+        });
+        choices.into_iter().map(|(_, alternatives)| alternatives).collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +96,7 @@ pub struct GroupConfig {
     symbol: SymbolRef,
     unique: bool,
     count: Option<Count>,
+    choice: Option<usize>,
 }
 
 impl GroupConfig {
@@ -70,21 +104,41 @@ impl GroupConfig {
     pub const fn symbol(&self) -> SymbolRef {
         self.symbol
     }
+
+    #[must_use]
+    pub const fn unique(&self) -> bool {
+        self.unique
+    }
+
+    #[must_use]
+    pub const fn count(&self) -> Option<Count> {
+        self.count
+    }
+
+    /// Index of the `one_of(...)` entry this group is an alternative of.
+    #[must_use]
+    pub const fn choice(&self) -> Option<usize> {
+        self.choice
+    }
 }
 
 #[derive(Debug)]
 pub struct UnresolvedGroupConfig {
+    position: usize,
     symbol: UnresolvedType,
     unique: bool,
     count: Option<Count>,
+    choice: Option<usize>,
 }
 
 #[derive(Debug)]
 pub struct UnresolvedGroupSymbol {
     identifier: String,
+    doc: Option<String>,
     extend: bool,
+    ordered: bool,
     unresolved: Vec<UnresolvedGroupConfig>,
-    resolved: Vec<GroupConfig>,
+    resolved: Vec<(usize, GroupConfig)>,
     metadata: HashMap<String, Option<BaseType>>,
 }
 
@@ -99,26 +153,40 @@ impl UnresolvedGroupSymbol {
 
         let extend = g.annotations.try_take("extend").is_some();
 
+        // A `one_of(...)` entry is expanded into one config per alternative
         let unresolved = g
             .entries
             .iter()
-            .map(|g| {
-                let identifier = g.name.to_string();
-                UnresolvedGroupConfig {
-                    symbol: UnresolvedType {
-                        generic_base: None,
-                        namespace: None,
-                        identifier,
-                    },
-                    unique: g.unique,
-                    count: g.count,
-                }
+            .enumerate()
+            .flat_map(|(index, entry)| {
+                let (names, choice) = if entry.one_of.is_empty() {
+                    (vec![entry.name.as_str()], None)
+                } else {
+                    (entry.one_of.iter().map(String::as_str).collect(), Some(index))
+                };
+                names
+                    .into_iter()
+                    .map(move |identifier| (identifier.to_string(), entry, choice))
+            })
+            .enumerate()
+            .map(|(position, (identifier, entry, choice))| UnresolvedGroupConfig {
+                position,
+                symbol: UnresolvedType {
+                    namespace: None,
+                    identifier,
+                    arguments: vec![],
+                },
+                unique: entry.unique,
+                count: entry.count,
+                choice,
             })
             .collect::<Vec<_>>();
 
         UnresolvedGroupSymbol {
             identifier,
+            doc: g.doc.clone(),
             extend,
+            ordered: g.ordered,
             metadata,
             unresolved,
             resolved: vec![],
@@ -135,18 +203,26 @@ impl TypeResolver<GroupSymbol> for UnresolvedGroupSymbol {
         self.unresolved.retain(|f| {
             if f.symbol.identifier == self.identifier {
                 let symbol = workspace.create_self_reference(&f.symbol);
-                self.resolved.push(GroupConfig {
-                    symbol,
-                    unique: f.unique,
-                    count: f.count,
-                });
+                self.resolved.push((
+                    f.position,
+                    GroupConfig {
+                        symbol,
+                        unique: f.unique,
+                        count: f.count,
+                        choice: f.choice,
+                    },
+                ));
                 return false;
             } else if let Some(symbol) = workspace.get_type(&f.symbol) {
-                self.resolved.push(GroupConfig {
-                    symbol,
-                    unique: f.unique,
-                    count: f.count,
-                });
+                self.resolved.push((
+                    f.position,
+                    GroupConfig {
+                        symbol,
+                        unique: f.unique,
+                        count: f.count,
+                        choice: f.choice,
+                    },
+                ));
                 return false;
             }
 
@@ -157,10 +233,16 @@ impl TypeResolver<GroupSymbol> for UnresolvedGroupSymbol {
     }
 
     fn as_resolved_type(&self) -> GroupSymbol {
+        // Entries resolve in any order, but ordered groups rely on the declaration order
+        let mut groups = self.resolved.clone();
+        groups.sort_by_key(|(position, _)| *position);
         GroupSymbol {
             identifier: self.identifier.clone(),
+            doc: self.doc.clone(),
             extend: self.extend,
-            groups: self.resolved.clone(),
+            ordered: self.ordered,
+            groups: groups.into_iter().map(|(_, group)| group).collect(),
+            metadata: self.metadata.clone(),
         }
     }
 }
@@ -170,18 +252,15 @@ impl Symbol for GroupSymbol {
         &self.identifier
     }
 
+    fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     fn try_get_self_reference(&self, model: &SchemaModel) -> Option<&SymbolRef> {
-        for group in &self.groups {
-            let ty = model.get_type_by_ref(group.symbol()).unwrap().expect("Unreachable!");
-            match ty {
-                SymbolKind::Lazy(lazy) => {
-                    if lazy.identifier == self.identifier {
-                        return Some(&group.symbol);
-                    }
-                }
-                _ => return None,
-            }
-        }
-        None
+        // Entries keep their declaration order, so the self reference may be any of them
+        self.groups.iter().map(|group| &group.symbol).find(|symbol| {
+            let ty = model.get_type_by_ref(**symbol).unwrap().expect("Unreachable!");
+            matches!(ty, SymbolKind::Lazy(lazy) if lazy.identifier == self.identifier)
+        })
     }
 }
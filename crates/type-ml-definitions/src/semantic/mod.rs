@@ -1,20 +1,33 @@
+mod alias;
+mod color;
+mod constant;
+mod constraint;
 mod element;
 mod enumeration;
 mod expression;
 mod group;
 mod loader;
 mod model;
+mod spread;
 mod structure;
 mod symbol;
 mod unresolved_schema;
 
+pub use alias::AliasSymbol;
+pub use color::Color;
+pub use constant::ConstantSymbol;
+pub use constraint::{Constraints, Length, Range};
+pub use element::ElementSymbol;
+pub use enumeration::{EnumSymbol, EnumVariant, Pattern};
 pub use expression::{ExpressionField, ExpressionSymbol};
 pub use group::{GroupConfig, GroupSymbol};
-pub(crate) use loader::LoadError;
+pub use loader::LoadError;
 pub use model::SchemaModel;
-pub use symbol::{Symbol, SymbolRef};
+pub use structure::StructSymbol;
+pub use symbol::{Char, MapSymbol, OptionSymbol, Symbol, SymbolKind, SymbolRef};
 
-use crate::semantic::symbol::{LazySymbol, SymbolKind};
+use crate::semantic::spread::InheritanceGraph;
+use crate::semantic::symbol::{LazySymbol, TypeRef};
 use crate::semantic::{loader::load_tmd, unresolved_schema::UnresolvedSchema};
 use std::collections::HashMap;
 use std::fmt::Debug;
@@ -22,9 +35,10 @@ use url::Url;
 
 #[derive(Debug)]
 pub struct UnresolvedType {
-    generic_base: Option<String>,
     namespace: Option<String>,
     identifier: String,
+    /// Arguments of a generic type, empty otherwise.
+    arguments: Vec<UnresolvedType>,
 }
 
 #[derive(Debug)]
@@ -34,6 +48,9 @@ pub struct AnalysisWorkspace {
     model: SchemaModel,
 
     namespace_stack: Vec<usize>,
+    /// Per loading schema, the built-in types it declares in their place.
+    shadowed_stack: Vec<Vec<String>>,
+    inheritance: InheritanceGraph,
     unresolved: HashMap<String, UnresolvedSchema>,
 }
 
@@ -46,6 +63,8 @@ impl AnalysisWorkspace {
             unresolved: HashMap::default(),
             model: SchemaModel::default(),
             namespace_stack: vec![],
+            shadowed_stack: vec![],
+            inheritance: InheritanceGraph::default(),
         }
     }
 
@@ -87,7 +106,20 @@ impl AnalysisWorkspace {
         let namespace = unresolved_module.namespace();
         let namespace_id = self.get_or_add_namespace_id(namespace);
 
+        let namespace_name = self.model.namespaces()[namespace_id].clone();
+        for (identifier, bases) in unresolved_module.struct_spreads() {
+            self.inheritance.add(&namespace_name, identifier, bases);
+        }
+        self.inheritance.check_cycles()?;
+
+        let shadowed = unresolved_module
+            .identifiers()
+            .filter(|identifier| self.model.is_shadowable_builtin(identifier))
+            .map(str::to_string)
+            .collect();
+
         self.namespace_stack.push(namespace_id);
+        self.shadowed_stack.push(shadowed);
         loop {
             let mut symbols = unresolved_module.resolve(self)?;
             if symbols.is_empty() {
@@ -107,10 +139,12 @@ impl AnalysisWorkspace {
                 true
             });
 
-            let vec = self.model.modules.get_mut(namespace_id).expect("Unreachable!");
-            vec.extend(symbols);
+            symbols.into_iter().for_each(|symbol| {
+                self.model.add_symbol(namespace_id, symbol);
+            });
         }
         self.namespace_stack.pop();
+        self.shadowed_stack.pop();
 
         Ok(())
     }
@@ -126,10 +160,7 @@ impl AnalysisWorkspace {
                 return id;
             }
 
-            let id = self.model.namespaces.len();
-            self.model.namespaces.push(ns.to_string());
-            self.model.modules.push(Vec::new());
-            return id;
+            return self.model.add_namespace(ns);
         }
 
         if let Some(id) = self.namespace_stack.last() {
@@ -140,6 +171,62 @@ impl AnalysisWorkspace {
     }
 
     fn get_type(&mut self, ty: &UnresolvedType) -> Option<SymbolRef> {
+        self.get_type_ref(ty, &[]).map(|ty| ty.as_concrete())
+    }
+
+    /// Resolves a type used inside a generic definition with `parameters`. An alias resolves to its target.
+    /// Generic types whose arguments don't refer to a parameter are instantiated right away.
+    fn get_type_ref(&mut self, ty: &UnresolvedType, parameters: &[String]) -> Option<TypeRef> {
+        if ty.namespace.is_none() && ty.arguments.is_empty() && parameters.contains(&ty.identifier) {
+            return Some(TypeRef::Generic(ty.identifier.clone()));
+        }
+
+        let symbol_ref = self.find_type(ty)?;
+        let symbol = self.model.get_type_by_ref(symbol_ref).unwrap()?;
+        match symbol {
+            SymbolKind::Generic(generic) if generic.parameters().len() == ty.arguments.len() => {}
+            SymbolKind::Generic(_) => return None,
+            SymbolKind::Alias(alias) if ty.arguments.is_empty() => return Some(TypeRef::Concrete(alias.target())),
+            _ if ty.arguments.is_empty() => return Some(TypeRef::Concrete(symbol_ref)),
+            _ => return None,
+        }
+
+        let arguments = ty
+            .arguments
+            .iter()
+            .map(|argument| self.get_type_ref(argument, parameters))
+            .collect::<Option<Vec<_>>>()?;
+
+        if arguments
+            .iter()
+            .all(|argument| matches!(argument, TypeRef::Concrete(_)))
+        {
+            let arguments = arguments.iter().map(TypeRef::as_concrete).collect();
+            self.model.instantiate(symbol_ref, arguments).map(TypeRef::Concrete)
+        } else {
+            Some(TypeRef::Instance(symbol_ref, arguments))
+        }
+    }
+
+    fn find_type(&mut self, ty: &UnresolvedType) -> Option<SymbolRef> {
+        //A built-in the schema declares itself is not found until that declaration resolves.
+        if ty.namespace.is_none()
+            && self
+                .shadowed_stack
+                .last()
+                .is_some_and(|shadowed| shadowed.contains(&ty.identifier))
+            && self.model.is_shadowable_builtin(&ty.identifier)
+            && self
+                .find_type_in(ty)
+                .is_some_and(|symbol_ref| symbol_ref.namespace == 0)
+        {
+            return None;
+        }
+
+        self.find_type_in(ty)
+    }
+
+    fn find_type_in(&mut self, ty: &UnresolvedType) -> Option<SymbolRef> {
         let mut array = [0usize, 0usize];
         let namespace = self.get_or_add_namespace_id(ty.namespace.as_deref());
         let mut ns_iter = if let Some(last) = self.namespace_stack.last()
@@ -156,39 +243,24 @@ impl AnalysisWorkspace {
             iter
         };
 
-        let identifier = if let Some(generic) = &ty.generic_base {
-            format!("{generic}_{}", ty.identifier)
-        } else {
-            ty.identifier.clone()
-        };
-
         ns_iter.find_map(|namespace| {
             let namespace = *namespace;
-            if let Some(id) = self.model.get_type_id(namespace, &identifier) {
-                Some(SymbolRef { namespace, id })
-            } else if let Some(generic) = &ty.generic_base
-                && let Some(generic) = self.model.get_type_by_name(namespace, generic).unwrap()
-                && let Some((target_ref, target)) = self.model.get_type_by_name(0, &ty.identifier).unwrap_with_ref()
-            {
-                let generic = generic.as_generic_symbol();
-                let ct = generic.construct_type(target, target_ref);
-                self.model.add_symbol(namespace, ct);
-                None
-            } else {
-                None
-            }
+            self.model
+                .get_type_id(namespace, &ty.identifier)
+                .map(|id| SymbolRef { namespace, id })
         })
     }
 
     fn create_self_reference(&mut self, ty: &UnresolvedType) -> SymbolRef {
         let namespace = self.get_or_add_namespace_id(ty.namespace.as_deref());
-        let type_table = self.model.get_mut_type_table_by_namespace_id(namespace);
-        let id = type_table.len();
-        type_table.push(SymbolKind::Lazy(LazySymbol {
-            source: id,
-            identifier: ty.identifier.clone(),
-        }));
-        SymbolRef { namespace, id }
+        let source = self.model.get_type_table_by_namespace_id(namespace).len();
+        self.model.add_symbol(
+            namespace,
+            SymbolKind::Lazy(LazySymbol {
+                source,
+                identifier: ty.identifier.clone(),
+            }),
+        )
     }
 }
 
@@ -1,20 +1,59 @@
+use crate::semantic::constant::ConstantSymbol;
+use crate::semantic::constraint::Constraints;
 use crate::semantic::element::ElementSymbol;
 use crate::semantic::expression::ExpressionSymbol;
 use crate::semantic::group::GroupSymbol;
 use crate::semantic::symbol::{
-    Bool, F32, F64, GenericSymbol, I8, I16, I32, I64, Str, Symbol, SymbolKind, SymbolRef, U8, U16, U32, U64,
+    Bool, Char, ColorSymbol, F32, F64, GenericSymbol, I8, I16, I32, I64, I128, Isize, OptionSymbol, PathSymbol, Str,
+    Symbol, SymbolKind, SymbolRef, U8, U16, U32, U64, U128, UrlSymbol, Usize,
 };
+use rustc_hash::FxHashMap;
 use std::collections::HashMap;
 
+/// An interned identifier.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct Name(usize);
+
+/// The identifier, type, constraints and default of a field.
+type FieldInfo<'a> = (&'a str, SymbolRef, &'a Constraints, Option<&'a str>);
+
+#[derive(Debug, Default)]
+struct Interner {
+    names: FxHashMap<Box<str>, Name>,
+}
+
+impl Interner {
+    fn intern(&mut self, name: &str) -> Name {
+        if let Some(name) = self.names.get(name) {
+            return *name;
+        }
+
+        let interned = Name(self.names.len());
+        self.names.insert(name.into(), interned);
+        interned
+    }
+
+    fn get(&self, name: &str) -> Option<Name> {
+        self.names.get(name).copied()
+    }
+}
+
 #[derive(Debug)]
 pub struct SchemaModel {
-    pub namespaces: Vec<String>,
-    pub modules: Vec<Vec<SymbolKind>>,
+    namespaces: Vec<String>,
+    modules: Vec<Vec<SymbolKind>>,
+
+    interner: Interner,
+    namespace_ids: FxHashMap<Name, usize>,
+    /// Per namespace map from symbol identifier to its index in the module. `SymbolRef::id` is that index.
+    symbol_ids: Vec<FxHashMap<Name, usize>>,
+    /// Constructed generic types, keyed by the generic symbol and its arguments.
+    instances: FxHashMap<(SymbolRef, Vec<SymbolRef>), SymbolRef>,
 }
 
 impl Default for SchemaModel {
     fn default() -> Self {
-        let global = vec![
+        let global = [
             SymbolKind::Bool(Bool),
             SymbolKind::F32(F32),
             SymbolKind::F64(F64),
@@ -26,15 +65,34 @@ impl Default for SchemaModel {
             SymbolKind::U16(U16),
             SymbolKind::U32(U32),
             SymbolKind::U64(U64),
+            SymbolKind::I128(I128),
+            SymbolKind::U128(U128),
+            SymbolKind::Isize(Isize),
+            SymbolKind::Usize(Usize),
+            SymbolKind::Char(Char),
             SymbolKind::String(Str),
+            SymbolKind::Color(ColorSymbol),
+            SymbolKind::Path(PathSymbol),
+            SymbolKind::Url(UrlSymbol),
             SymbolKind::Generic(Box::new(GenericSymbol::option())),
             SymbolKind::Generic(Box::new(GenericSymbol::array())),
+            SymbolKind::Generic(Box::new(GenericSymbol::map())),
         ];
 
-        Self {
-            namespaces: vec![String::new()], //The empty string is the global namespace.
-            modules: vec![global],
-        }
+        let mut model = Self {
+            namespaces: vec![],
+            modules: vec![],
+            interner: Interner::default(),
+            namespace_ids: FxHashMap::default(),
+            symbol_ids: vec![],
+            instances: FxHashMap::default(),
+        };
+
+        model.add_namespace(""); //The empty string is the global namespace.
+        global.into_iter().for_each(|symbol| {
+            model.add_symbol(0, symbol);
+        });
+        model
     }
 }
 
@@ -55,7 +113,7 @@ impl SchemaModel {
             .zip(self.namespaces.iter())
             .try_for_each(|(module, namespace)| {
                 let mut occurrences: HashMap<&str, usize> = HashMap::new();
-                for kind in module {
+                for kind in module.iter().filter(|kind| !is_shadowable(kind)) {
                     *occurrences.entry(kind.identifier()).or_default() += 1;
                 }
 
@@ -74,15 +132,196 @@ impl SchemaModel {
         Ok(())
     }
 
+    /// Checks that the key of every constructed map is `String` or an enum.
+    fn validate_map_keys(&self) -> Result<(), crate::Error> {
+        for symbol in self.modules.iter().flatten() {
+            let SymbolKind::Map(map) = symbol else {
+                continue;
+            };
+
+            if !matches!(
+                self.get_type_by_ref(map.key()).as_ref(),
+                SymbolKind::String(_) | SymbolKind::Enum(_)
+            ) {
+                return Err(crate::Error::InvalidMapKey {
+                    map: map.identifier().to_string(),
+                    key: self.type_path(map.key()),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the value of every constant can be parsed as its type.
+    fn validate_constants(&self) -> Result<(), crate::Error> {
+        for (namespace, module) in self.modules.iter().enumerate() {
+            for (id, symbol) in module.iter().enumerate() {
+                let SymbolKind::Constant(constant) = symbol else {
+                    continue;
+                };
+
+                self.check_scalar(constant.value(), constant.ty())
+                    .and_then(|()| {
+                        self.get_type_by_ref(constant.ty())
+                            .as_ref()
+                            .can_parse(constant.value(), self)
+                    })
+                    .map_err(|source| crate::Error::InvalidConstant {
+                        constant: self.type_path(SymbolRef { namespace, id }),
+                        source: Box::new(source),
+                    })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Defaults and constants are plain values. Structs, lists and maps are only written in layouts.
+    fn check_scalar(&self, value: &str, ty: SymbolRef) -> Result<(), crate::Error> {
+        let kind = |ty| self.get_type_by_ref(ty).unwrap().expect("Unreachable!");
+        let kind = match kind(ty) {
+            SymbolKind::Option(option) if !OptionSymbol::is_none(value) => kind(option.inner()),
+            other => other,
+        };
+
+        match kind {
+            SymbolKind::Struct(_) | SymbolKind::Array(_) | SymbolKind::Map(_) => {
+                Err(crate::Error::StructuredValue(self.type_path(ty)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The identifier, type, constraints and default of every field of a struct, element or expression.
+    fn fields(symbol: &SymbolKind) -> Option<Vec<FieldInfo<'_>>> {
+        let fields = match symbol {
+            SymbolKind::Struct(symbol) => symbol
+                .fields
+                .iter()
+                .map(|f| (f.identifier(), f.ty(), f.constraints(), f.default()))
+                .collect(),
+            SymbolKind::Element(symbol) => symbol
+                .fields()
+                .iter()
+                .map(|f| (f.identifier(), f.ty(), f.constraints(), f.default()))
+                .collect(),
+            SymbolKind::Expression(symbol) => symbol
+                .fields()
+                .iter()
+                .map(|f| (f.identifier(), f.ty(), f.constraints(), f.default()))
+                .collect(),
+            _ => return None,
+        };
+
+        Some(fields)
+    }
+
+    fn field_path(namespace: &str, symbol: &SymbolKind, field: &str) -> String {
+        let symbol = symbol.identifier();
+        if namespace.is_empty() {
+            format!("{symbol}::{field}")
+        } else {
+            format!("{namespace}::{symbol}::{field}")
+        }
+    }
+
+    /// Checks that every field default can be parsed as the field type and satisfies the field constraints.
+    fn validate_defaults(&self) -> Result<(), crate::Error> {
+        for (namespace_id, (namespace, module)) in self.namespaces.iter().zip(&self.modules).enumerate() {
+            for symbol in module {
+                let Some(fields) = Self::fields(symbol) else {
+                    continue;
+                };
+
+                for (field, ty, constraints, default) in fields {
+                    let Some(default) = default else {
+                        continue;
+                    };
+
+                    self.expand_constant(default, ty, namespace_id)
+                        .and_then(|default| {
+                            self.check_scalar(default, ty)?;
+                            self.get_type_by_ref(ty).as_ref().can_parse(default, self)?;
+                            constraints.check(default)
+                        })
+                        .map_err(|source| crate::Error::InvalidDefaultValue {
+                            field: Self::field_path(namespace, symbol, field),
+                            source: Box::new(source),
+                        })?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that `@range` is only applied to numbers, `@len` to strings and arrays and `@pattern` to strings.
+    fn validate_constraints(&self) -> Result<(), crate::Error> {
+        for (namespace, module) in self.namespaces.iter().zip(&self.modules) {
+            for symbol in module {
+                let Some(fields) = Self::fields(symbol) else {
+                    continue;
+                };
+
+                for (field, ty, constraints, _) in fields {
+                    let kind = |ty| self.get_type_by_ref(ty).unwrap().expect("Unreachable!");
+                    let kind = match kind(ty) {
+                        SymbolKind::Option(option) => kind(option.inner()),
+                        other => other,
+                    };
+
+                    let numeric = matches!(
+                        kind,
+                        SymbolKind::F32(_)
+                            | SymbolKind::F64(_)
+                            | SymbolKind::I8(_)
+                            | SymbolKind::I16(_)
+                            | SymbolKind::I32(_)
+                            | SymbolKind::I64(_)
+                            | SymbolKind::U8(_)
+                            | SymbolKind::U16(_)
+                            | SymbolKind::U32(_)
+                            | SymbolKind::U64(_)
+                            | SymbolKind::I128(_)
+                            | SymbolKind::U128(_)
+                            | SymbolKind::Isize(_)
+                            | SymbolKind::Usize(_)
+                    );
+                    let string = matches!(kind, SymbolKind::String(_));
+                    let array = matches!(kind, SymbolKind::Array(_));
+
+                    let misplaced = [
+                        ("range", constraints.range().is_some() && !numeric),
+                        ("len", constraints.length().is_some() && !string && !array),
+                        ("pattern", constraints.pattern().is_some() && !string),
+                    ];
+                    if let Some((annotation, _)) = misplaced.into_iter().find(|(_, misplaced)| *misplaced) {
+                        return Err(crate::Error::MisplacedAnnotation {
+                            annotation: annotation.to_string(),
+                            field: Self::field_path(namespace, symbol, field),
+                            ty: self.type_path(ty),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn post_load(&mut self) -> Result<(), crate::Error> {
         self.find_duplicate_identifiers()?;
+        self.validate_map_keys()?;
+        self.validate_constants()?;
+        self.validate_constraints()?;
+        self.validate_defaults()?;
 
         let root_ref = self.get_root_group_ref()?;
-        let global = self.modules.first_mut().unwrap();
 
         //Create main group
         let main_group = GroupSymbol::main(root_ref);
-        global.push(SymbolKind::Group(main_group));
+        self.add_symbol(0, SymbolKind::Group(main_group));
 
         //TODO Remove generic Array type
         /*
@@ -109,16 +348,10 @@ impl SchemaModel {
 
     #[must_use]
     pub fn get_type_by_name(&self, namespace: usize, name: &str) -> TypeQuery<'_> {
-        let type_table = self.get_type_table_by_namespace_id(namespace);
-        let ty = type_table
-            .iter()
-            .enumerate()
-            .find(|(_, kind)| kind.identifier() == name);
-
-        if let Some((id, kind)) = ty {
+        if let Some(id) = self.get_type_id(namespace, name) {
             TypeQuery {
                 symbol_ref: SymbolRef { namespace, id },
-                kind: Some(kind),
+                kind: Some(&self.modules[namespace][id]),
             }
         } else {
             TypeQuery {
@@ -128,36 +361,138 @@ impl SchemaModel {
         }
     }
 
+    /// Returns the identifier of the symbol prefixed with its namespace, unless it's in the global namespace.
+    #[must_use]
+    pub fn type_path(&self, symbol_ref: SymbolRef) -> String {
+        let identifier = self
+            .get_type_by_ref(symbol_ref)
+            .unwrap()
+            .expect("Unreachable!")
+            .identifier();
+        let namespace = self.get_namespace_by_id(symbol_ref.namespace);
+        if namespace.is_empty() {
+            identifier.to_string()
+        } else {
+            format!("{namespace}::{identifier}")
+        }
+    }
+
+    /// Finds the constant `path` refers to. A constant without a namespace is looked up in `namespace`, then in the
+    /// global namespace.
+    #[must_use]
+    pub fn get_constant(&self, path: &str, namespace: usize) -> Option<(SymbolRef, &ConstantSymbol)> {
+        let (namespaces, name) = match path.rsplit_once("::") {
+            Some((namespace, name)) => (vec![self.try_get_namespace_id(Some(namespace))?], name),
+            None => (vec![namespace, 0], path),
+        };
+
+        namespaces.into_iter().find_map(|namespace| {
+            let id = self.get_type_id(namespace, name)?;
+            match &self.modules[namespace][id] {
+                SymbolKind::Constant(constant) => Some((SymbolRef { namespace, id }, constant)),
+                _ => None,
+            }
+        })
+    }
+
+    /// Replaces a constant name, looked up from `namespace`, with the constant's value; other values are returned
+    /// as they are. A constant declared with another type than `ty` or, if `ty` is an `Option`, its inner type is
+    /// not expanded, so a value such as an enum variant with the same name still parses.
+    pub fn expand_constant<'a>(
+        &'a self,
+        value: &'a str,
+        ty: SymbolRef,
+        namespace: usize,
+    ) -> Result<&'a str, crate::Error> {
+        let Some((symbol_ref, constant)) = self.get_constant(value.trim(), namespace) else {
+            return Ok(value);
+        };
+
+        let inner = match self.get_type_by_ref(ty).as_ref() {
+            SymbolKind::Option(option) => Some(option.inner()),
+            _ => None,
+        };
+        if constant.ty() == ty || Some(constant.ty()) == inner {
+            return Ok(constant.value());
+        }
+
+        if self.get_type_by_ref(ty).as_ref().can_parse(value, self).is_ok() {
+            return Ok(value);
+        }
+
+        Err(crate::Error::ConstantTypeMismatch {
+            constant: self.type_path(symbol_ref),
+            expected: self.type_path(ty),
+            actual: self.type_path(constant.ty()),
+        })
+    }
+
+    /// Returns the type constructed from the generic symbol `generic` with `arguments`, constructing it on first use.
+    /// Returns `None` if `generic` is not a generic symbol or takes a different number of arguments.
+    pub(crate) fn instantiate(&mut self, generic: SymbolRef, arguments: Vec<SymbolRef>) -> Option<SymbolRef> {
+        let key = (generic, arguments);
+        if let Some(instance) = self.instances.get(&key) {
+            return Some(*instance);
+        }
+
+        let Some(SymbolKind::Generic(symbol)) = self.get_type_by_ref(generic).unwrap() else {
+            return None;
+        };
+        if symbol.parameters().len() != key.1.len() {
+            return None;
+        }
+
+        let symbol = symbol.clone();
+        let instance = symbol.construct_type(&key.1, self);
+        let instance = self.add_symbol(generic.namespace, instance);
+        self.instances.insert(key, instance);
+        Some(instance)
+    }
+
     pub(crate) fn get_namespace_by_id(&self, namespace: usize) -> &str {
         self.namespaces[namespace].as_str()
     }
 
+    #[must_use]
+    pub fn namespaces(&self) -> &[String] {
+        &self.namespaces
+    }
+
+    #[must_use]
+    pub fn modules(&self) -> &[Vec<SymbolKind>] {
+        &self.modules
+    }
+
     pub fn get_namespace_id(&self, namespace: Option<&str>) -> Result<usize, crate::Error> {
-        if let Some(ns) = namespace {
-            self.namespaces
-                .iter()
-                .enumerate()
-                .find(|(_, n)| *n == ns)
-                .map(|(id, _)| id)
-                .ok_or(crate::Error::NamespaceNotFound(ns.to_string()))
-        } else {
-            Ok(0)
-        }
+        self.try_get_namespace_id(namespace)
+            .ok_or_else(|| crate::Error::NamespaceNotFound(namespace.unwrap_or_default().to_string()))
     }
 
     #[must_use]
     pub fn try_get_namespace_id(&self, namespace: Option<&str>) -> Option<usize> {
         if let Some(ns) = namespace {
-            self.namespaces
-                .iter()
-                .enumerate()
-                .find(|(_, n)| *n == ns)
-                .map(|(id, _)| id)
+            let name = self.interner.get(ns)?;
+            self.namespace_ids.get(&name).copied()
         } else {
             Some(0)
         }
     }
 
+    /// Returns the id of `namespace`, registering it with an empty module if it's new.
+    pub(crate) fn add_namespace(&mut self, namespace: &str) -> usize {
+        let name = self.interner.intern(namespace);
+        if let Some(id) = self.namespace_ids.get(&name) {
+            return *id;
+        }
+
+        let id = self.namespaces.len();
+        self.namespaces.push(namespace.to_string());
+        self.modules.push(Vec::new());
+        self.symbol_ids.push(FxHashMap::default());
+        self.namespace_ids.insert(name, id);
+        id
+    }
+
     pub fn get_type_table_by_namespace_name(&self, namespace: Option<&str>) -> Result<&[SymbolKind], crate::Error> {
         let id = self.get_namespace_id(namespace)?;
         Ok(self.get_type_table_by_namespace_id(id))
@@ -168,22 +503,10 @@ impl SchemaModel {
         self.modules.get(namespace).expect("Unreachable!")
     }
 
-    pub fn get_mut_type_table_by_namespace_name(
-        &mut self,
-        namespace: Option<&str>,
-    ) -> Result<&mut Vec<SymbolKind>, crate::Error> {
-        let id = self.get_namespace_id(namespace)?;
-        Ok(self.get_mut_type_table_by_namespace_id(id))
-    }
-
-    pub fn get_mut_type_table_by_namespace_id(&mut self, namespace: usize) -> &mut Vec<SymbolKind> {
-        self.modules.get_mut(namespace).expect("Unreachable!")
-    }
-
     #[must_use]
     pub fn get_type_id(&self, namespace: usize, name: &str) -> Option<usize> {
-        let type_table = self.get_type_table_by_namespace_id(namespace);
-        type_table.iter().position(|t| t.identifier() == name)
+        let name = self.interner.get(name)?;
+        self.symbol_ids.get(namespace)?.get(&name).copied()
     }
 
     pub fn get_type_by_id(&self, namespace: Option<&str>, id: usize) -> Result<Option<&SymbolKind>, crate::Error> {
@@ -191,40 +514,54 @@ impl SchemaModel {
         Ok(type_table.get(id))
     }
 
-    pub fn add_symbol(&mut self, namespace: usize, symbol: SymbolKind) {
-        let type_table = self.get_mut_type_table_by_namespace_id(namespace);
+    pub fn add_symbol(&mut self, namespace: usize, symbol: SymbolKind) -> SymbolRef {
+        let name = self.interner.intern(symbol.identifier());
+        let type_table = self.modules.get_mut(namespace).expect("Unreachable!");
+        let id = type_table.len();
         type_table.push(symbol);
+        let ids = &mut self.symbol_ids[namespace];
+        match ids.get(&name) {
+            Some(&existing) if is_shadowable(&type_table[existing]) => {
+                ids.insert(name, id);
+            }
+            Some(_) => {}
+            None => {
+                ids.insert(name, id);
+            }
+        }
+        SymbolRef { namespace, id }
+    }
+
+    /// Whether `identifier` still names a global built-in that a schema may declare in its place.
+    pub(crate) fn is_shadowable_builtin(&self, identifier: &str) -> bool {
+        self.get_type_id(0, identifier)
+            .is_some_and(|id| is_shadowable(&self.modules[0][id]))
     }
 
     pub fn replace_type(&mut self, symbol_ref: &SymbolRef, kind: SymbolKind) {
-        let type_table = self.get_mut_type_table_by_namespace_id(symbol_ref.namespace);
-        type_table[symbol_ref.id] = kind;
+        let SymbolRef { namespace, id } = *symbol_ref;
+        let previous = self.interner.intern(self.modules[namespace][id].identifier());
+        let name = self.interner.intern(kind.identifier());
+        if previous != name && self.symbol_ids[namespace].get(&previous) == Some(&id) {
+            self.symbol_ids[namespace].remove(&previous);
+        }
+
+        self.modules[namespace][id] = kind;
+        self.symbol_ids[namespace].entry(name).or_insert(id);
     }
 
     pub fn get_root_group_ref(&self) -> Result<SymbolRef, crate::Error> {
-        let (namespace, id) = self
-            .modules
-            .iter()
-            .enumerate()
-            .find_map(|(namespace, array)| {
-                array
-                    .iter()
-                    .position(|k| k.identifier() == "Root")
-                    .map(|id| (namespace, id))
+        (0..self.modules.len())
+            .find_map(|namespace| {
+                self.get_type_id(namespace, "Root")
+                    .map(|id| SymbolRef { namespace, id })
             })
-            .ok_or(crate::Error::RootGroupNotFound)?;
-
-        Ok(SymbolRef { namespace, id })
+            .ok_or(crate::Error::RootGroupNotFound)
     }
 
     #[must_use]
     pub fn get_main_group_ref(&self) -> SymbolRef {
-        let id = self
-            .modules
-            .iter()
-            .find_map(|array| array.iter().position(|k| k.identifier() == "Main"))
-            .expect("Unreachable!");
-
+        let id = self.get_type_id(0, "Main").expect("Unreachable!");
         SymbolRef { namespace: 0, id }
     }
 }
@@ -293,3 +630,8 @@ impl<'a> TypeQuery<'a> {
         }
     }
 }
+
+/// Built-in types a schema can replace by declaring a type with the same name.
+const fn is_shadowable(kind: &SymbolKind) -> bool {
+    matches!(kind, SymbolKind::Color(_) | SymbolKind::Path(_) | SymbolKind::Url(_))
+}
@@ -0,0 +1,139 @@
+use crate::ast::TypeRef;
+use crate::semantic::structure::ResolvedField;
+use crate::{AnalysisWorkspace, Error, Symbol, SymbolKind, UnresolvedType};
+use std::collections::HashMap;
+
+/// The `..Base` entries of a struct, element or expression block. Every base must be a struct; its fields are
+/// inherited unless the block declares a field with the same name.
+#[derive(Debug, Default)]
+pub struct Spreads {
+    pending: Vec<UnresolvedType>,
+    resolved: Vec<(String, Vec<ResolvedField>)>,
+}
+
+impl Spreads {
+    pub fn new(spreads: &[TypeRef]) -> Self {
+        Self {
+            pending: spreads.iter().cloned().map(Into::into).collect(),
+            resolved: vec![],
+        }
+    }
+
+    /// The bases that aren't resolved yet.
+    pub fn pending(&self) -> impl Iterator<Item = &UnresolvedType> {
+        self.pending.iter()
+    }
+
+    /// Resolves the bases that are already in the model. Returns `true` when every base is resolved.
+    pub fn resolve(&mut self, workspace: &mut AnalysisWorkspace) -> Result<bool, Error> {
+        let mut pending = vec![];
+        for ty in std::mem::take(&mut self.pending) {
+            let Some(symbol_ref) = workspace.get_type(&ty) else {
+                pending.push(ty);
+                continue;
+            };
+
+            match workspace.model.get_type_by_ref(symbol_ref).as_ref() {
+                SymbolKind::Struct(base) => self.resolved.push((base.identifier().to_string(), base.fields.clone())),
+                SymbolKind::Lazy(_) => pending.push(ty),
+                _ => return Err(Error::InvalidSpread(ty.identifier)),
+            }
+        }
+
+        self.pending = pending;
+        Ok(self.pending.is_empty())
+    }
+
+    /// Takes the inherited fields in the order of the bases, skipping the `declared` ones.
+    /// A field that two bases provide must be declared by the block itself.
+    pub fn take_inherited(&mut self, declared: &[&str]) -> Result<Vec<ResolvedField>, Error> {
+        let resolved = std::mem::take(&mut self.resolved);
+        let mut origins: HashMap<&str, &str> = HashMap::new();
+        let mut fields = vec![];
+        for (base, base_fields) in &resolved {
+            for field in base_fields {
+                if declared.contains(&field.identifier()) {
+                    continue;
+                }
+
+                if let Some(first) = origins.insert(field.identifier(), base) {
+                    return Err(Error::ConflictingField {
+                        field: field.identifier().to_string(),
+                        first: first.to_string(),
+                        second: base.clone(),
+                    });
+                }
+                fields.push(field.clone());
+            }
+        }
+
+        Ok(fields)
+    }
+}
+
+fn full_path(namespace: &str, identifier: &str) -> String {
+    if namespace.is_empty() {
+        identifier.to_string()
+    } else {
+        format!("{namespace}::{identifier}")
+    }
+}
+
+/// The `..Base` spreads of every struct loaded so far, keyed by the namespace-qualified path of the struct.
+#[derive(Debug, Default)]
+pub struct InheritanceGraph {
+    structs: Vec<String>,
+    /// For every base, the paths it may refer to in lookup order.
+    bases: HashMap<String, Vec<Vec<String>>>,
+}
+
+impl InheritanceGraph {
+    pub fn add<'a>(&mut self, namespace: &str, identifier: &str, bases: impl IntoIterator<Item = &'a UnresolvedType>) {
+        let bases = bases
+            .into_iter()
+            .map(|base| match &base.namespace {
+                Some(base_namespace) => vec![full_path(base_namespace, &base.identifier)],
+                None if namespace.is_empty() => vec![base.identifier.clone()],
+                None => vec![full_path(namespace, &base.identifier), base.identifier.clone()],
+            })
+            .collect();
+
+        let path = full_path(namespace, identifier);
+        self.structs.push(path.clone());
+        self.bases.insert(path, bases);
+    }
+
+    /// Checks that no struct inherits from itself through its `..Base` spreads, in one schema or across several.
+    pub fn check_cycles(&self) -> Result<(), Error> {
+        let mut done = vec![];
+        for start in &self.structs {
+            let mut path = vec![];
+            self.visit(start, &mut path, &mut done)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit<'a>(&'a self, node: &'a str, path: &mut Vec<&'a str>, done: &mut Vec<&'a str>) -> Result<(), Error> {
+        if done.contains(&node) {
+            return Ok(());
+        }
+
+        if let Some(start) = path.iter().position(|n| *n == node) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(node);
+            return Err(Error::InheritanceCycle(cycle.join(" -> ")));
+        }
+
+        path.push(node);
+        for candidates in self.bases.get(node).into_iter().flatten() {
+            if let Some(base) = candidates.iter().find(|base| self.bases.contains_key(*base)) {
+                self.visit(base, path, done)?;
+            }
+        }
+        path.pop();
+        done.push(node);
+
+        Ok(())
+    }
+}
@@ -1,15 +1,18 @@
+use crate::Constraints;
 use crate::ast::TypeIdent;
 use crate::ast::TypeRef;
 use crate::ast::{BaseType, Field, Struct};
+use crate::semantic::spread::Spreads;
 use crate::{
     AnalysisWorkspace, SchemaModel, TypeResolver, UnresolvedType,
-    semantic::symbol::{Symbol, SymbolRef},
+    semantic::symbol::{self, GenericBase, GenericSymbol, Symbol, SymbolRef},
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone)]
 pub struct StructSymbol {
     pub identifier: String,
+    pub doc: Option<String>,
     pub fields: Vec<ResolvedField>,
     pub metadata: HashMap<String, Option<BaseType>>,
 }
@@ -17,78 +20,194 @@ pub struct StructSymbol {
 #[derive(Debug, Clone)]
 pub struct ResolvedField {
     identifier: String,
+    doc: Option<String>,
     ty: SymbolRef,
+    constraints: Constraints,
+    default: Option<String>,
+}
+
+impl ResolvedField {
+    #[must_use]
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    /// The `///` comment of the declaration.
+    #[must_use]
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
+    #[must_use]
+    pub fn ty(&self) -> SymbolRef {
+        self.ty
+    }
+
+    #[must_use]
+    pub fn constraints(&self) -> &Constraints {
+        &self.constraints
+    }
+
+    #[must_use]
+    pub fn default(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+
+    /// A field must be written unless it has a default or an `Option` type, which defaults to `None`.
+    #[must_use]
+    pub fn is_required(&self, model: &SchemaModel) -> bool {
+        self.default.is_none() && !model.get_type_by_ref(self.ty).as_ref().is_option_symbol()
+    }
+}
+
+/// A field of a generic struct. Its type may refer to the parameters of the struct.
+#[derive(Debug, Clone)]
+pub struct GenericField {
+    identifier: String,
+    doc: Option<String>,
+    ty: symbol::TypeRef,
+    constraints: Constraints,
+    default: Option<String>,
+}
+
+impl GenericField {
+    #[must_use]
+    pub fn ty(&self) -> &symbol::TypeRef {
+        &self.ty
+    }
+
+    /// An inherited field, which has a concrete type.
+    fn inherited(field: ResolvedField) -> Self {
+        Self {
+            identifier: field.identifier,
+            doc: field.doc,
+            ty: symbol::TypeRef::Concrete(field.ty),
+            constraints: field.constraints,
+            default: field.default,
+        }
+    }
+
+    pub(crate) fn with_type(&self, ty: SymbolRef) -> ResolvedField {
+        ResolvedField {
+            identifier: self.identifier.clone(),
+            doc: self.doc.clone(),
+            ty,
+            constraints: self.constraints.clone(),
+            default: self.default.clone(),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct UnresolvedStructField {
     identifier: String,
+    doc: Option<String>,
     ty: UnresolvedType,
+    constraints: Constraints,
+    default: Option<String>,
 }
 
 impl From<TypeRef> for UnresolvedType {
     fn from(value: TypeRef) -> Self {
         match value.ident {
             TypeIdent::Simple(ident) => Self {
-                generic_base: None,
                 namespace: value.namespace,
                 identifier: ident,
+                arguments: vec![],
             },
-            TypeIdent::Generic(ident, inner) => Self {
-                generic_base: Some(ident),
+            TypeIdent::Generic(ident, arguments) => Self {
                 namespace: value.namespace,
-                identifier: inner.to_string(), //TODO
+                identifier: ident,
+                arguments: arguments.into_iter().map(Into::into).collect(),
             },
         }
     }
 }
 
 impl UnresolvedStructField {
-    pub fn new(f: &Field) -> UnresolvedStructField {
+    pub fn new(f: &Field) -> Result<UnresolvedStructField, crate::Error> {
         let identifier = f.name.to_string();
         let ty = f.ty.clone().into();
-        UnresolvedStructField { identifier, ty }
+        let constraints = Constraints::from_annotations(&f.annotations)?;
+        let default = f.default.clone();
+        Ok(UnresolvedStructField {
+            identifier,
+            doc: f.doc.clone(),
+            ty,
+            constraints,
+            default,
+        })
     }
 }
 
 #[derive(Debug)]
 pub struct UnresolvedStructSymbol {
     identifier: String,
+    doc: Option<String>,
+    generics: Vec<String>,
     pub fields: Vec<UnresolvedStructField>,
+    pub spreads: Spreads,
     pub metadata: HashMap<String, Option<BaseType>>,
-    pub resolved: Vec<ResolvedField>,
+    pub resolved: Vec<GenericField>,
 }
 
 impl UnresolvedStructSymbol {
-    pub fn new(s: &Struct) -> UnresolvedStructSymbol {
+    pub fn new(s: &Struct) -> Result<UnresolvedStructSymbol, crate::Error> {
         let identifier = s.name.clone();
-        let fields = s.fields.iter().map(UnresolvedStructField::new).collect::<Vec<_>>();
+        let fields = s
+            .fields
+            .iter()
+            .map(UnresolvedStructField::new)
+            .collect::<Result<Vec<_>, _>>()?;
         let mut metadata = HashMap::new();
 
         s.attributes.iter().for_each(|a| {
             metadata.insert(a.name.clone(), a.value.clone());
         });
 
-        UnresolvedStructSymbol {
+        Ok(UnresolvedStructSymbol {
             identifier,
+            doc: s.doc.clone(),
+            generics: s.generics.clone(),
             fields,
+            spreads: Spreads::new(&s.spreads),
             metadata,
             resolved: vec![],
-        }
+        })
     }
 
     pub const fn identifier(&self) -> &str {
         self.identifier.as_str()
     }
+
+    pub const fn is_generic(&self) -> bool {
+        !self.generics.is_empty()
+    }
+
+    pub fn as_generic_symbol(&self) -> GenericSymbol {
+        assert!(self.fields.is_empty());
+        GenericSymbol::new(
+            self.identifier.clone(),
+            self.generics.clone(),
+            GenericBase::Struct {
+                doc: self.doc.clone(),
+                fields: self.resolved.clone(),
+                metadata: self.metadata.clone(),
+            },
+        )
+    }
 }
 
 impl TypeResolver<StructSymbol> for UnresolvedStructSymbol {
     fn resolve(&mut self, workspace: &mut AnalysisWorkspace) -> Result<bool, crate::Error> {
         self.fields.retain(|f| {
-            if let Some(ty) = workspace.get_type(&f.ty) {
-                self.resolved.push(ResolvedField {
+            if let Some(ty) = workspace.get_type_ref(&f.ty, &self.generics) {
+                self.resolved.push(GenericField {
                     identifier: f.identifier.clone(),
+                    doc: f.doc.clone(),
                     ty,
+                    constraints: f.constraints.clone(),
+                    default: f.default.clone(),
                 });
                 return false;
             }
@@ -96,14 +215,23 @@ impl TypeResolver<StructSymbol> for UnresolvedStructSymbol {
             true
         });
 
-        Ok(self.fields.is_empty())
+        if !self.spreads.resolve(workspace)? || !self.fields.is_empty() {
+            return Ok(false);
+        }
+
+        let declared = self.resolved.iter().map(|f| f.identifier.as_str()).collect::<Vec<_>>();
+        let inherited = self.spreads.take_inherited(&declared)?;
+        self.resolved
+            .splice(0..0, inherited.into_iter().map(GenericField::inherited));
+        Ok(true)
     }
 
     fn as_resolved_type(&self) -> StructSymbol {
         assert!(self.fields.is_empty());
         StructSymbol {
             identifier: self.identifier.clone(),
-            fields: self.resolved.clone(),
+            doc: self.doc.clone(),
+            fields: self.resolved.iter().map(|f| f.with_type(f.ty.as_concrete())).collect(),
             metadata: self.metadata.clone(),
         }
     }
@@ -114,35 +242,71 @@ impl Symbol for StructSymbol {
         &self.identifier
     }
 
+    fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     fn can_parse(&self, value: &str, model: &SchemaModel) -> Result<(), crate::Error> {
-        let mut result = true;
-        value
-            .trim()
-            .split(',')
-            .map(str::trim)
-            .filter(|&s| !s.is_empty())
-            .try_for_each(|field| {
-                let mut parts = field.split(':');
-                let name = parts.next().expect("Unreachable!").trim();
-                let value = parts.next().expect("Unreachable!").trim();
-                if let Some(field) = self.field(name) {
-                    let ty = model.get_type_by_ref(field.ty).unwrap().expect("Unreachable!");
-                    ty.can_parse(value, model)?;
-                } else {
-                    result = false;
-                }
-
-                Ok::<(), crate::Error>(())
-            })?;
-        if result {
-            Ok(())
-        } else {
-            Err(crate::Error::InvalidArgumentType(
-                "Struct".to_string(),
-                value.to_string(),
-            ))
+        let value = value.trim();
+        let inner = value
+            .strip_prefix('{')
+            .and_then(|v| v.strip_suffix('}'))
+            .unwrap_or(value);
+
+        let mut used_fields = HashSet::new();
+        for field in split_fields(inner) {
+            let Some((name, value)) = field.split_once(':') else {
+                return Err(crate::Error::InvalidArgumentType(
+                    value.to_string(),
+                    self.identifier.clone(),
+                ));
+            };
+
+            let name = name.trim();
+            let field = self
+                .field(name)
+                .ok_or_else(|| crate::Error::FieldNotFound(name.to_string()))?;
+            if !used_fields.insert(name) {
+                return Err(crate::Error::DuplicateField(name.to_string()));
+            }
+
+            let ty = model.get_type_by_ref(field.ty);
+            ty.as_ref().can_parse(value.trim(), model)?;
+            field.constraints.check(value)?;
+        }
+
+        if let Some(field) = self
+            .fields
+            .iter()
+            .find(|f| f.is_required(model) && !used_fields.contains(f.identifier.as_str()))
+        {
+            return Err(crate::Error::MissingRequiredField(field.identifier.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits `a: 1, b: "x, y", c: { d: 2 }` on the commas that aren't inside strings, nested braces or brackets.
+pub(crate) fn split_fields(value: &str) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '{' | '[' if !in_string => depth += 1,
+            '}' | ']' if !in_string => depth = depth.saturating_sub(1),
+            ',' if !in_string && depth == 0 => {
+                fields.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => {}
         }
     }
+    fields.push(&value[start..]);
+    fields.into_iter().map(str::trim).filter(|f| !f.is_empty()).collect()
 }
 
 impl StructSymbol {
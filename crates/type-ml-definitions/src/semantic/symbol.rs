@@ -1,14 +1,21 @@
 use crate::Error;
+use crate::ast::BaseType;
+use crate::semantic::alias::AliasSymbol;
+use crate::semantic::color::Color;
+use crate::semantic::constant::ConstantSymbol;
 use crate::semantic::expression::ExpressionSymbol;
 use crate::semantic::{
     element::ElementSymbol,
     enumeration::{EnumSymbol, EnumVariant},
     group::GroupSymbol,
     model::SchemaModel,
-    structure::StructSymbol,
+    structure::{GenericField, StructSymbol, split_fields},
 };
 use enum_dispatch::enum_dispatch;
-use std::{collections::HashMap, fmt::Debug};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+};
 
 #[derive(Default, Debug, Copy, Clone, Hash, PartialEq, Eq)]
 pub struct SymbolRef {
@@ -19,14 +26,17 @@ pub struct SymbolRef {
 #[derive(Debug, Clone)]
 pub enum TypeRef {
     Concrete(SymbolRef),
+    /// A parameter of the enclosing generic definition.
     Generic(String),
+    /// A generic type applied to arguments that refer to parameters, e.g. `Array<T>`.
+    Instance(SymbolRef, Vec<TypeRef>),
 }
 
 impl TypeRef {
     pub fn as_concrete(&self) -> SymbolRef {
         match self {
             TypeRef::Concrete(symbol_ref) => *symbol_ref,
-            TypeRef::Generic(_) => unreachable!(),
+            TypeRef::Generic(_) | TypeRef::Instance(..) => unreachable!(),
         }
     }
 }
@@ -35,6 +45,10 @@ impl TypeRef {
 #[enum_dispatch]
 pub trait Symbol {
     fn identifier(&self) -> &str;
+    /// The `///` comment of the declaration, if it has one.
+    fn doc(&self) -> Option<&str> {
+        None
+    }
     fn can_parse(&self, value: &str, model: &SchemaModel) -> Result<(), Error> {
         Err(Error::TypeIsNotParsable)
     }
@@ -71,6 +85,10 @@ impl_symbol!(U8, "u8", str::parse::<u8>);
 impl_symbol!(U16, "u16", str::parse::<u16>);
 impl_symbol!(U32, "u32", str::parse::<u32>);
 impl_symbol!(U64, "u64", str::parse::<u64>);
+impl_symbol!(I128, "i128", str::parse::<i128>);
+impl_symbol!(U128, "u128", str::parse::<u128>);
+impl_symbol!(Isize, "isize", str::parse::<isize>);
+impl_symbol!(Usize, "usize", str::parse::<usize>);
 
 #[derive(Debug, Clone)]
 pub struct Str;
@@ -93,6 +111,85 @@ impl Symbol for Str {
     }
 }
 
+/// Returns the text between the quotes of a `"..."` literal.
+fn unquote(value: &str) -> Option<&str> {
+    value.strip_prefix('"').and_then(|v| v.strip_suffix('"'))
+}
+
+/// A single character, written as `'c'` or `"c"`.
+#[derive(Debug, Clone)]
+pub struct Char;
+
+impl Char {
+    #[must_use]
+    pub fn parse(value: &str) -> Option<char> {
+        let value = value.trim();
+        let inner = unquote(value).or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))?;
+        inner.parse().ok()
+    }
+}
+
+impl Symbol for Char {
+    fn identifier(&self) -> &'static str {
+        "char"
+    }
+
+    fn can_parse(&self, value: &str, _: &SchemaModel) -> Result<(), Error> {
+        Self::parse(value)
+            .map(|_| ())
+            .ok_or_else(|| Error::InvalidArgumentType(value.to_string(), "char".to_string()))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ColorSymbol;
+
+impl Symbol for ColorSymbol {
+    fn identifier(&self) -> &'static str {
+        "Color"
+    }
+
+    fn can_parse(&self, value: &str, _: &SchemaModel) -> Result<(), Error> {
+        Color::parse(value)
+            .map(|_| ())
+            .ok_or_else(|| Error::InvalidArgumentType(value.to_string(), "Color".to_string()))
+    }
+}
+
+/// A non-empty `"..."` path to an asset.
+#[derive(Debug, Clone)]
+pub struct PathSymbol;
+
+impl Symbol for PathSymbol {
+    fn identifier(&self) -> &'static str {
+        "Path"
+    }
+
+    fn can_parse(&self, value: &str, _: &SchemaModel) -> Result<(), Error> {
+        match unquote(value.trim()) {
+            Some(path) if !path.trim().is_empty() => Ok(()),
+            _ => Err(Error::InvalidArgumentType(value.to_string(), "Path".to_string())),
+        }
+    }
+}
+
+/// An absolute `"..."` URL.
+#[derive(Debug, Clone)]
+pub struct UrlSymbol;
+
+impl Symbol for UrlSymbol {
+    fn identifier(&self) -> &'static str {
+        "Url"
+    }
+
+    fn can_parse(&self, value: &str, _: &SchemaModel) -> Result<(), Error> {
+        match unquote(value.trim()).map(url::Url::parse) {
+            Some(Ok(_)) => Ok(()),
+            _ => Err(Error::InvalidArgumentType(value.to_string(), "Url".to_string())),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ArraySymbol {
     identifier: String,
@@ -108,112 +205,297 @@ impl Default for ArraySymbol {
     }
 }
 
+impl ArraySymbol {
+    #[must_use]
+    pub const fn inner(&self) -> SymbolRef {
+        self.inner
+    }
+}
+
 impl Symbol for ArraySymbol {
     fn identifier(&self) -> &str {
         &self.identifier
     }
 
     fn can_parse(&self, value: &str, model: &SchemaModel) -> Result<(), Error> {
-        value.split(',').map(str::trim).try_for_each(|value| {
-            let kind = model.get_type_by_ref(self.inner).unwrap().unwrap();
-            kind.can_parse(value, model)?;
-            Ok::<_, Error>(())
-        })?;
+        let Some(inner) = value.trim().strip_prefix('[').and_then(|v| v.strip_suffix(']')) else {
+            return Err(Error::InvalidArgumentType(value.to_string(), self.identifier.clone()));
+        };
+
+        let kind = model.get_type_by_ref(self.inner).unwrap().expect("Unreachable!");
+        split_fields(inner)
+            .into_iter()
+            .try_for_each(|value| kind.can_parse(value, model))
+    }
+}
+
+/// An instance of the built-in `Option<T>`. A value is either `None` or a bare value of the inner type, which
+/// stands for `Some`. `None` takes precedence over an enum variant with the same name.
+#[derive(Debug, Clone)]
+pub struct OptionSymbol {
+    identifier: String,
+    inner: SymbolRef,
+}
+
+impl OptionSymbol {
+    #[must_use]
+    pub const fn inner(&self) -> SymbolRef {
+        self.inner
+    }
+
+    #[must_use]
+    pub fn is_none(value: &str) -> bool {
+        value.trim() == "None"
+    }
+}
+
+impl Symbol for OptionSymbol {
+    fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    fn can_parse(&self, value: &str, model: &SchemaModel) -> Result<(), Error> {
+        if Self::is_none(value) {
+            return Ok(());
+        }
+
+        let kind = model.get_type_by_ref(self.inner).unwrap().expect("Unreachable!");
+        kind.can_parse(value, model)
+    }
+}
+
+/// An instance of the built-in `Map<K, V>`. A value is written as `{ "key": value, ... }`; keys are strings or
+/// enum variants and must be unique.
+#[derive(Debug, Clone)]
+pub struct MapSymbol {
+    identifier: String,
+    key: SymbolRef,
+    value: SymbolRef,
+}
+
+impl MapSymbol {
+    #[must_use]
+    pub const fn key(&self) -> SymbolRef {
+        self.key
+    }
+
+    #[must_use]
+    pub const fn value(&self) -> SymbolRef {
+        self.value
+    }
+
+    /// Checks that every key is a value of the key type and that no key is repeated.
+    pub fn validate_keys<'a>(&self, keys: impl IntoIterator<Item = &'a str>, model: &SchemaModel) -> Result<(), Error> {
+        let kind = model.get_type_by_ref(self.key).unwrap().expect("Unreachable!");
+        let mut used_keys = HashSet::new();
+        for key in keys {
+            let key = key.trim();
+            kind.can_parse(key, model)?;
+            if !used_keys.insert(key) {
+                return Err(Error::DuplicateKey(key.to_string()));
+            }
+        }
+
         Ok(())
     }
+
+    /// Splits `"key": value` on the first colon outside a string.
+    fn split_entry(entry: &str) -> Option<(&str, &str)> {
+        let mut in_string = false;
+        let (i, _) = entry.char_indices().find(|(_, c)| match c {
+            '"' => {
+                in_string = !in_string;
+                false
+            }
+            ':' => !in_string,
+            _ => false,
+        })?;
+        Some((&entry[..i], &entry[i + 1..]))
+    }
 }
 
+impl Symbol for MapSymbol {
+    fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    fn can_parse(&self, value: &str, model: &SchemaModel) -> Result<(), Error> {
+        let Some(inner) = value.trim().strip_prefix('{').and_then(|v| v.strip_suffix('}')) else {
+            return Err(Error::InvalidArgumentType(value.to_string(), self.identifier.clone()));
+        };
+
+        let entries = split_fields(inner)
+            .into_iter()
+            .map(|entry| {
+                Self::split_entry(entry)
+                    .ok_or_else(|| Error::InvalidArgumentType(value.to_string(), self.identifier.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.validate_keys(entries.iter().map(|(key, _)| *key), model)?;
+
+        let kind = model.get_type_by_ref(self.value).unwrap().expect("Unreachable!");
+        entries
+            .iter()
+            .try_for_each(|(_, value)| kind.can_parse(value.trim(), model))
+    }
+}
+
+/// A generic struct, enum, array, option or map. Concrete types are constructed per list of arguments by
+/// [`SchemaModel::instantiate`].
 #[derive(Debug, Clone)]
 pub struct GenericSymbol {
-    base: SymbolKind,
+    identifier: String,
+    parameters: Vec<String>,
+    base: GenericBase,
+}
+
+/// The definition of a generic symbol. Its types may refer to the parameters with [`TypeRef::Generic`].
+#[derive(Debug, Clone)]
+pub enum GenericBase {
+    Array(TypeRef),
+    Option(TypeRef),
+    Map(TypeRef, TypeRef),
+    Struct {
+        doc: Option<String>,
+        fields: Vec<GenericField>,
+        metadata: HashMap<String, Option<BaseType>>,
+    },
+    Enum {
+        doc: Option<String>,
+        variants: Vec<EnumVariant>,
+        metadata: HashMap<String, Option<BaseType>>,
+    },
 }
 
 impl GenericSymbol {
     #[must_use]
-    pub const fn new(base: SymbolKind) -> Self {
-        Self { base }
+    pub const fn new(identifier: String, parameters: Vec<String>, base: GenericBase) -> Self {
+        Self {
+            identifier,
+            parameters,
+            base,
+        }
     }
 
     #[must_use]
     pub fn option() -> Self {
-        let variants = vec![
-            EnumVariant {
-                identifier: "Some".to_string(),
-                ty: Some(TypeRef::Generic("T".to_string())),
-                pattern: None,
-            },
-            EnumVariant {
-                identifier: "None".to_string(),
-                ty: None,
-                pattern: None,
-            },
-        ];
-        GenericSymbol {
-            base: SymbolKind::Enum(EnumSymbol {
-                identifier: "Option".to_string(),
-                variants,
-                metadata: HashMap::default(),
-            }),
-        }
+        Self::new(
+            "Option".to_string(),
+            vec!["T".to_string()],
+            GenericBase::Option(TypeRef::Generic("T".to_string())),
+        )
     }
 
+    #[must_use]
     pub fn array() -> Self {
-        Self {
-            base: SymbolKind::Array(ArraySymbol::default()),
-        }
+        Self::new(
+            "Array".to_string(),
+            vec!["T".to_string()],
+            GenericBase::Array(TypeRef::Generic("T".to_string())),
+        )
     }
 
     #[must_use]
-    pub fn construct_type(&self, other: &SymbolKind, other_ref: SymbolRef) -> SymbolKind {
+    pub fn map() -> Self {
+        Self::new(
+            "Map".to_string(),
+            vec!["K".to_string(), "V".to_string()],
+            GenericBase::Map(TypeRef::Generic("K".to_string()), TypeRef::Generic("V".to_string())),
+        )
+    }
+
+    #[must_use]
+    pub fn parameters(&self) -> &[String] {
+        &self.parameters
+    }
+
+    /// Builds the concrete type for `arguments`, instantiating the generic types used by the definition.
+    /// The number of arguments must match the parameters.
+    pub(crate) fn construct_type(&self, arguments: &[SymbolRef], model: &mut SchemaModel) -> SymbolKind {
+        let names = arguments.iter().map(|a| model.type_path(*a)).collect::<Vec<_>>();
+        let identifier = format!("{}<{}>", self.identifier, names.join(", "));
         match &self.base {
-            SymbolKind::Array(_) => SymbolKind::Array(ArraySymbol {
-                identifier: format!("Array_{}", other.identifier()),
-                inner: other_ref,
+            GenericBase::Array(inner) => SymbolKind::Array(ArraySymbol {
+                identifier,
+                inner: self.substitute(inner, arguments, model),
+            }),
+            GenericBase::Option(inner) => SymbolKind::Option(OptionSymbol {
+                identifier,
+                inner: self.substitute(inner, arguments, model),
             }),
-            SymbolKind::Struct(value) => SymbolKind::Struct(StructSymbol {
-                identifier: format!("{}_{}", value.identifier(), other.identifier()),
-                fields: value.fields.clone(),
-                metadata: value.metadata.clone(),
+            GenericBase::Map(key, value) => SymbolKind::Map(MapSymbol {
+                identifier,
+                key: self.substitute(key, arguments, model),
+                value: self.substitute(value, arguments, model),
+            }),
+            GenericBase::Struct { doc, fields, metadata } => SymbolKind::Struct(StructSymbol {
+                identifier,
+                doc: doc.clone(),
+                fields: fields
+                    .iter()
+                    .map(|f| f.with_type(self.substitute(f.ty(), arguments, model)))
+                    .collect(),
+                metadata: metadata.clone(),
             }),
-            SymbolKind::Enum(value) => {
-                let variants = value
-                    .variants
+            GenericBase::Enum {
+                doc,
+                variants,
+                metadata,
+            } => SymbolKind::Enum(EnumSymbol {
+                identifier,
+                doc: doc.clone(),
+                variants: variants
                     .iter()
-                    .map(|var| {
-                        let ty = var.ty.as_ref().map(|ty| match ty {
-                            TypeRef::Concrete(concrete) => TypeRef::Concrete(*concrete),
-                            TypeRef::Generic(_) => TypeRef::Concrete(other_ref),
-                        });
-                        EnumVariant {
-                            identifier: var.identifier.clone(),
-                            ty,
-                            pattern: var.pattern.clone(),
-                        }
+                    .map(|var| EnumVariant {
+                        identifier: var.identifier.clone(),
+                        doc: var.doc.clone(),
+                        ty: var
+                            .ty
+                            .as_ref()
+                            .map(|ty| TypeRef::Concrete(self.substitute(ty, arguments, model))),
+                        pattern: var.pattern.clone(),
                     })
-                    .collect::<Vec<_>>();
-                SymbolKind::Enum(EnumSymbol {
-                    identifier: format!("{}_{}", value.identifier(), other.identifier()),
-                    variants,
-                    metadata: value.metadata.clone(),
-                })
+                    .collect(),
+                metadata: metadata.clone(),
+            }),
+        }
+    }
+
+    fn substitute(&self, ty: &TypeRef, arguments: &[SymbolRef], model: &mut SchemaModel) -> SymbolRef {
+        match ty {
+            TypeRef::Concrete(symbol_ref) => *symbol_ref,
+            TypeRef::Generic(parameter) => {
+                let index = self.parameters.iter().position(|p| p == parameter);
+                arguments[index.expect("Unreachable!")]
+            }
+            TypeRef::Instance(generic, inner) => {
+                let inner = inner.iter().map(|ty| self.substitute(ty, arguments, model)).collect();
+                model.instantiate(*generic, inner).expect("Unreachable!")
             }
-            SymbolKind::Generic(_) => todo!("Make type construction"),
-            SymbolKind::Group(_) => unimplemented!("The group does not support generics"),
-            SymbolKind::Element(_) => unimplemented!("The element does not support generics"),
-            _ => unreachable!("The default types does not support generics"), // Default types
         }
     }
 }
 
 impl Symbol for GenericSymbol {
     fn identifier(&self) -> &str {
-        self.base.identifier()
+        &self.identifier
+    }
+
+    fn doc(&self) -> Option<&str> {
+        match &self.base {
+            GenericBase::Struct { doc, .. } | GenericBase::Enum { doc, .. } => doc.as_deref(),
+            GenericBase::Array(_) | GenericBase::Option(_) | GenericBase::Map(..) => None,
+        }
     }
 }
 
 impl Symbol for Box<GenericSymbol> {
     fn identifier(&self) -> &str {
-        self.base.identifier()
+        &self.identifier
+    }
+
+    fn doc(&self) -> Option<&str> {
+        self.as_ref().doc()
     }
 }
 
@@ -243,7 +525,15 @@ pub enum SymbolKind {
     U16(U16),
     U32(U32),
     U64(U64),
+    I128(I128),
+    U128(U128),
+    Isize(Isize),
+    Usize(Usize),
+    Char(Char),
     String(Str),
+    Color(ColorSymbol),
+    Path(PathSymbol),
+    Url(UrlSymbol),
     Generic(Box<GenericSymbol>),
     Struct(StructSymbol),
     Enum(EnumSymbol),
@@ -251,6 +541,10 @@ pub enum SymbolKind {
     Element(ElementSymbol),
     Expression(ExpressionSymbol),
     Array(ArraySymbol),
+    Option(OptionSymbol),
+    Map(MapSymbol),
+    Alias(AliasSymbol),
+    Constant(ConstantSymbol),
     Lazy(LazySymbol),
 }
 
@@ -294,4 +588,8 @@ impl SymbolKind {
     pub fn is_expression_symbol(&self) -> bool {
         matches!(self, SymbolKind::Expression(_))
     }
+
+    pub fn is_option_symbol(&self) -> bool {
+        matches!(self, SymbolKind::Option(_))
+    }
 }
@@ -1,7 +1,9 @@
 use crate::ast::CustomType;
+use crate::semantic::alias::UnresolvedAliasSymbol;
+use crate::semantic::constant::UnresolvedConstantSymbol;
 use crate::semantic::expression::UnresolvedExpressionSymbol;
 use crate::{
-    AnalysisWorkspace, RmlxParser, SchemaAst, TypeResolver,
+    AnalysisWorkspace, RmlxParser, SchemaAst, TypeResolver, UnresolvedType,
     semantic::{
         element::UnresolvedElementSymbol, enumeration::UnresolvedEnumSymbol, group::UnresolvedGroupSymbol,
         structure::UnresolvedStructSymbol, symbol::SymbolKind,
@@ -18,6 +20,9 @@ pub struct UnresolvedSchema {
     enums: Vec<UnresolvedEnumSymbol>,
     structs: Vec<UnresolvedStructSymbol>,
     elements: Vec<UnresolvedElementSymbol>,
+
+    aliases: Vec<UnresolvedAliasSymbol>,
+    constants: Vec<UnresolvedConstantSymbol>,
 }
 
 impl UnresolvedSchema {
@@ -34,8 +39,8 @@ impl UnresolvedSchema {
             .iter()
             .filter(|t| t.is_enum())
             .map(CustomType::unwrap_enum)
-            .map(UnresolvedEnumSymbol::new)
-            .collect::<Vec<_>>();
+            .map(|e| UnresolvedEnumSymbol::new(e, source, path))
+            .collect::<Result<Vec<_>, _>>()?;
 
         let structs = ast
             .custom_types
@@ -43,7 +48,7 @@ impl UnresolvedSchema {
             .filter(|t| t.is_struct())
             .map(CustomType::unwrap_struct)
             .map(UnresolvedStructSymbol::new)
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>, _>>()?;
 
         let groups = ast
             .custom_types
@@ -59,7 +64,7 @@ impl UnresolvedSchema {
             .filter(|t| t.is_element())
             .map(CustomType::unwrap_element)
             .map(UnresolvedElementSymbol::new)
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>, _>>()?;
 
         let expressions = ast
             .custom_types
@@ -67,6 +72,22 @@ impl UnresolvedSchema {
             .filter(|t| t.is_expression())
             .map(CustomType::unwrap_expression)
             .map(UnresolvedExpressionSymbol::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let aliases = ast
+            .custom_types
+            .iter()
+            .filter(|t| t.is_alias())
+            .map(CustomType::unwrap_alias)
+            .map(UnresolvedAliasSymbol::new)
+            .collect::<Vec<_>>();
+
+        let constants = ast
+            .custom_types
+            .iter()
+            .filter(|t| t.is_constant())
+            .map(CustomType::unwrap_constant)
+            .map(UnresolvedConstantSymbol::new)
             .collect::<Vec<_>>();
 
         Ok(UnresolvedSchema {
@@ -76,15 +97,37 @@ impl UnresolvedSchema {
             groups,
             elements,
             expressions,
+            aliases,
+            constants,
         })
     }
 
     pub fn resolve(&mut self, workspace: &mut AnalysisWorkspace) -> Result<Vec<SymbolKind>, crate::Error> {
         let mut symbols = vec![];
+        self.aliases.try_retain_mut(|a| {
+            let result = a.resolve(workspace)?;
+            if result {
+                symbols.push(SymbolKind::Alias(a.as_resolved_type()));
+            }
+            Ok::<bool, crate::Error>(!result)
+        })?;
+
+        self.constants.try_retain_mut(|c| {
+            let result = c.resolve(workspace)?;
+            if result {
+                symbols.push(SymbolKind::Constant(c.as_resolved_type()));
+            }
+            Ok::<bool, crate::Error>(!result)
+        })?;
+
         self.structs.try_retain_mut(|s| {
             let result = s.resolve(workspace)?;
             if result {
-                symbols.push(SymbolKind::Struct(s.as_resolved_type()));
+                symbols.push(if s.is_generic() {
+                    SymbolKind::Generic(Box::new(s.as_generic_symbol()))
+                } else {
+                    SymbolKind::Struct(s.as_resolved_type())
+                });
             }
             Ok::<bool, crate::Error>(!result)
         })?;
@@ -92,7 +135,11 @@ impl UnresolvedSchema {
         self.enums.try_retain_mut(|e| {
             let result = e.resolve(workspace)?;
             if result {
-                symbols.push(SymbolKind::Enum(e.as_resolved_type()));
+                symbols.push(if e.is_generic() {
+                    SymbolKind::Generic(Box::new(e.as_generic_symbol()))
+                } else {
+                    SymbolKind::Enum(e.as_resolved_type())
+                });
             }
             Ok::<bool, crate::Error>(!result)
         })?;
@@ -130,6 +177,26 @@ impl UnresolvedSchema {
             && self.groups.is_empty()
             && self.elements.is_empty()
             && self.expressions.is_empty()
+            && self.aliases.is_empty()
+            && self.constants.is_empty()
+    }
+
+    /// Identifiers of every symbol declared in the schema.
+    pub fn identifiers(&self) -> impl Iterator<Item = &str> {
+        self.groups
+            .iter()
+            .map(UnresolvedGroupSymbol::identifier)
+            .chain(self.expressions.iter().map(UnresolvedExpressionSymbol::identifier))
+            .chain(self.enums.iter().map(UnresolvedEnumSymbol::identifier))
+            .chain(self.structs.iter().map(UnresolvedStructSymbol::identifier))
+            .chain(self.elements.iter().map(UnresolvedElementSymbol::identifier))
+            .chain(self.aliases.iter().map(UnresolvedAliasSymbol::identifier))
+            .chain(self.constants.iter().map(UnresolvedConstantSymbol::identifier))
+    }
+
+    /// The unresolved `..Base` spreads of every struct.
+    pub fn struct_spreads(&self) -> impl Iterator<Item = (&str, impl Iterator<Item = &UnresolvedType>)> {
+        self.structs.iter().map(|s| (s.identifier(), s.spreads.pending()))
     }
 
     pub fn namespace(&self) -> Option<&str> {
@@ -157,6 +224,14 @@ impl UnresolvedSchema {
             return self.elements.first().map(UnresolvedElementSymbol::identifier);
         }
 
+        if !self.aliases.is_empty() {
+            return self.aliases.first().map(UnresolvedAliasSymbol::identifier);
+        }
+
+        if !self.constants.is_empty() {
+            return self.constants.first().map(UnresolvedConstantSymbol::identifier);
+        }
+
         None
     }
 }
@@ -164,6 +239,7 @@ impl UnresolvedSchema {
 struct DirectiveResult {
     namespace: Option<String>,
     uses: Vec<String>,
+    #[allow(dead_code)] //TODO Report directive errors
     errors: Vec<String>,
 }
 
@@ -0,0 +1,30 @@
+use tower_lsp::lsp_types::{Position, Url};
+use type_ml_definitions::doc_at;
+
+/// Converts `position` into a byte offset of `source`.
+fn offset_of(source: &str, position: Position) -> Option<usize> {
+    let line_start = if position.line == 0 {
+        0
+    } else {
+        source.match_indices('\n').nth(position.line as usize - 1)?.0 + 1
+    };
+    let line = source[line_start..].lines().next().unwrap_or_default();
+
+    // Positions count UTF-16 code units
+    let mut offset = 0;
+    let mut units = 0;
+    for c in line.chars() {
+        if units >= position.character as usize {
+            break;
+        }
+        units += c.len_utf16();
+        offset += c.len_utf8();
+    }
+
+    Some(line_start + offset)
+}
+
+/// Returns the `///` comment of what is named at `position` of the schema at `uri`.
+pub fn schema_doc(source: &str, uri: &Url, position: Position) -> Option<String> {
+    doc_at(source, uri, offset_of(source, position)?)
+}
@@ -1,7 +1,9 @@
 #![allow(clippy::cast_possible_truncation)]
 
+mod hover;
 mod tokens;
 
+use crate::hover::schema_doc;
 use crate::tokens::get_tokens;
 use lexer_core::CstNode;
 use std::collections::HashMap;
@@ -12,11 +14,11 @@ use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::{
     CompletionItem, CompletionOptions, CompletionParams, CompletionResponse, DeclarationCapability, DeclarationOptions,
     DeclarationRegistrationOptions, DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-    DocumentFilter, InitializeParams, InitializeResult, InitializedParams, InsertTextFormat, MessageType,
-    SemanticTokenType, SemanticTokens, SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions,
-    SemanticTokensParams, SemanticTokensResult, SemanticTokensServerCapabilities, ServerCapabilities,
-    StaticRegistrationOptions, TextDocumentRegistrationOptions, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
-    WorkDoneProgressOptions,
+    DocumentFilter, Hover, HoverContents, HoverParams, HoverProviderCapability, InitializeParams, InitializeResult,
+    InitializedParams, InsertTextFormat, MarkupContent, MarkupKind, MessageType, SemanticTokenType, SemanticTokens,
+    SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions, SemanticTokensParams, SemanticTokensResult,
+    SemanticTokensServerCapabilities, ServerCapabilities, StaticRegistrationOptions, TextDocumentRegistrationOptions,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url, WorkDoneProgressOptions,
 };
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 use type_ml_definitions::{AnalysisWorkspace, RmlxParser, SchemaAst};
@@ -25,6 +27,7 @@ struct Backend {
     client: Client,
 
     schemas: RwLock<HashMap<Url, AnalysisWorkspace>>, // RMLX files
+    documents: RwLock<HashMap<Url, String>>,          // Open files as last synced
     workspaces: RwLock<HashMap<Url, Workspace>>,      // RML  files
 }
 
@@ -63,6 +66,7 @@ impl LanguageServer for Backend {
                     },
                 )),
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
                 completion_provider: Some(CompletionOptions {
                     resolve_provider: Some(true),
                     trigger_characters: Some(vec!["#".to_string()]),
@@ -109,6 +113,10 @@ impl LanguageServer for Backend {
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
         let uri = params.text_document.uri;
         let extension = Path::new(uri.path()).extension().and_then(|e| e.to_str()).unwrap();
+        self.documents
+            .write()
+            .unwrap()
+            .insert(uri.clone(), params.text_document.text.clone());
 
         match extension {
             "tml" => {
@@ -126,7 +134,7 @@ impl LanguageServer for Backend {
                 );
                  */
             }
-            "tmld" => {
+            "tmd" | "tmld" => {
                 //let workspace = AnalysisWorkspace::new(uri.clone()).run().unwrap();
                 //let mut schemas = self.schemas.write().unwrap();
                 //schemas.insert(uri, workspace);
@@ -140,6 +148,7 @@ impl LanguageServer for Backend {
         let uri = params.text_document.uri;
         let text = &params.content_changes[0].text;
         let extension = Path::new(uri.path()).extension().and_then(OsStr::to_str).unwrap();
+        self.documents.write().unwrap().insert(uri.clone(), text.clone());
 
         match extension {
             "tml" => {
@@ -150,7 +159,7 @@ impl LanguageServer for Backend {
                 file.content.clone_from(text);
                  */
             }
-            "tmld" => {
+            "tmd" | "tmld" => {
                 //let workspace = AnalysisWorkspace::new(uri.clone()).run().unwrap();
                 //let mut schemas = self.schemas.write().unwrap();
                 //schemas.insert(uri, workspace);
@@ -161,7 +170,7 @@ impl LanguageServer for Backend {
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
-        let _ = params;
+        self.documents.write().unwrap().remove(&params.text_document.uri);
         //let mut write = self.workspaces.write().unwrap();
         //write.remove(&params.text_document.uri).unwrap();
     }
@@ -192,6 +201,27 @@ impl LanguageServer for Backend {
         })))
     }
 
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let position = params.text_document_position_params;
+        let uri = position.text_document.uri;
+        let extension = Path::new(uri.path()).extension().and_then(OsStr::to_str);
+        if !matches!(extension, Some("tmd" | "tmld")) {
+            return Ok(None);
+        }
+
+        let documents = self.documents.read().unwrap();
+        let doc = documents
+            .get(&uri)
+            .and_then(|source| schema_doc(source, &uri, position.position));
+        Ok(doc.map(|doc| Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: doc,
+            }),
+            range: None,
+        }))
+    }
+
     async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
         if let Some(context) = params.context {
             if let Some(trigger) = context.trigger_character {
@@ -229,6 +259,7 @@ async fn main() {
     let (service, socket) = LspService::new(|client| Backend {
         client,
         schemas: RwLock::default(),
+        documents: RwLock::default(),
         workspaces: RwLock::default(),
     });
     Server::new(stdin, stdout, socket).serve(service).await;
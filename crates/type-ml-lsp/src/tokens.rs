@@ -113,12 +113,37 @@ fn base_type_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
     });
 }
 
-//generic_type = { ident ~ LT ~ ns_ident ~ GT }
+//generic_type = { ns_ident ~ LT ~ type_ref ~ (COMMA ~ type_ref)* ~ GT }
 fn generic_type_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
     cst.children.iter().for_each(|f| {
         let token_type = match f.kind {
-            RmlxNode::Ident | RmlxNode::NsIdent => TYPE_TOKEN,
+            RmlxNode::GenericType => {
+                generic_type_tokens(f, tokens);
+                return;
+            }
+            RmlxNode::NsIdent => TYPE_TOKEN,
+            RmlxNode::GT | RmlxNode::LT => OPERATOR_TOKEN,
+            RmlxNode::Symbol => u32::MAX,
+            _ => unreachable!(),
+        };
+
+        tokens.push(SemanticToken {
+            delta_line: f.delta_line,
+            delta_start: f.delta_start,
+            length: f.text.len() as u32,
+            token_type,
+            token_modifiers_bitset: 0,
+        });
+    });
+}
+
+//generic_params = { LT ~ ident ~ (COMMA ~ ident)* ~ GT }
+fn generic_params_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
+    cst.children.iter().for_each(|f| {
+        let token_type = match f.kind {
+            RmlxNode::Ident => TYPE_TOKEN,
             RmlxNode::GT | RmlxNode::LT => OPERATOR_TOKEN,
+            RmlxNode::Symbol => u32::MAX,
             _ => unreachable!(),
         };
 
@@ -159,11 +184,12 @@ fn array_tokens(ancestor: &CstNode<RmlxNode>, cst: &CstNode<RmlxNode>, tokens: &
     });
 }
 
-//annotation_value  = { string | array }
+//annotation_value  = { string | array | annotation_args }
 fn annotation_value_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
     let token = cst.children.first().unwrap();
     let token_type = match token.kind {
         RmlxNode::String => STRING_TOKEN,
+        RmlxNode::AnnotationArgs => NUMBER_TOKEN,
         RmlxNode::Array => {
             array_tokens(cst, token, tokens);
             return;
@@ -213,7 +239,7 @@ fn annotation_tokens(ancestor: &CstNode<RmlxNode>, cst: &CstNode<RmlxNode>, toke
     });
 }
 
-//simple_field = { annotation* ~ ident ~ COLON ~ (generic_type | ns_ident) }
+//simple_field = { annotation* ~ ident ~ COLON ~ type_ref ~ (ASSIGN ~ default_value)? }
 fn simple_field(ancestor: &CstNode<RmlxNode>, cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
     let mut iter = cst.children.iter();
     let token = iter.next().unwrap();
@@ -238,6 +264,7 @@ fn simple_field(ancestor: &CstNode<RmlxNode>, cst: &CstNode<RmlxNode>, tokens: &
             RmlxNode::Symbol => u32::MAX,
             RmlxNode::NsIdent => TYPE_TOKEN,
             RmlxNode::Ident => PARAMETER_TOKEN,
+            RmlxNode::DefaultValue => NUMBER_TOKEN,
             _ => unreachable!(),
         };
 
@@ -251,7 +278,30 @@ fn simple_field(ancestor: &CstNode<RmlxNode>, cst: &CstNode<RmlxNode>, tokens: &
     });
 }
 
-//simple_fields = { simple_field ~ (COMMA ~ simple_field?)* ~ COMMA? }
+//spread = { SPREAD ~ type_ref }
+fn spread_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
+    cst.children.iter().for_each(|f| {
+        let token_type = match f.kind {
+            RmlxNode::GenericType => {
+                generic_type_tokens(f, tokens);
+                return;
+            }
+            RmlxNode::Symbol => OPERATOR_TOKEN,
+            RmlxNode::NsIdent => TYPE_TOKEN,
+            _ => unreachable!(),
+        };
+
+        tokens.push(SemanticToken {
+            delta_line: f.delta_line,
+            delta_start: f.delta_start,
+            length: f.text.len() as u32,
+            token_type,
+            token_modifiers_bitset: 0,
+        });
+    });
+}
+
+//simple_fields = { (spread | simple_field) ~ (COMMA ~ (spread | simple_field)?)* ~ COMMA? }
 fn simple_fields(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
     cst.children.iter().for_each(|f| {
         let token_type = match f.kind {
@@ -259,6 +309,10 @@ fn simple_fields(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
                 simple_field(cst, f, tokens);
                 return;
             }
+            RmlxNode::Spread => {
+                spread_tokens(f, tokens);
+                return;
+            }
             RmlxNode::Symbol => u32::MAX,
             _ => unreachable!(),
         };
@@ -305,7 +359,7 @@ fn block_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
     });
 }
 
-//struct = { attribute_list* ~ STRUCT ~ (generic_type | ident) ~ block }
+//struct = { attribute_list* ~ STRUCT ~ ident ~ generic_params? ~ (block | SEMI) }
 fn struct_tokens(ancestor: &CstNode<RmlxNode>, cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
     cst.children.iter().for_each(|f| {
         let token_type = match f.kind {
@@ -313,8 +367,8 @@ fn struct_tokens(ancestor: &CstNode<RmlxNode>, cst: &CstNode<RmlxNode>, tokens:
                 attribute_list_tokens(ancestor, f, tokens);
                 return;
             }
-            RmlxNode::GenericType => {
-                generic_type_tokens(f, tokens);
+            RmlxNode::GenericParams => {
+                generic_params_tokens(f, tokens);
                 return;
             }
             RmlxNode::Block => {
@@ -375,7 +429,7 @@ fn element_tokens(ancestor: &CstNode<RmlxNode>, cst: &CstNode<RmlxNode>, tokens:
     });
 }
 
-//enum_variant = { annotation* ~ ((ident ~ LPAREN ~ ns_ident ~ RPAREN) | ident) }
+//enum_variant = { annotation* ~ ((ident ~ LPAREN ~ type_ref ~ RPAREN) | ident) }
 fn enum_variant_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
     cst.children.iter().for_each(|f| {
         let token_type = match f.kind {
@@ -383,6 +437,10 @@ fn enum_variant_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>)
                 annotation_tokens(cst, f, tokens); //TODO fix
                 return;
             }
+            RmlxNode::GenericType => {
+                generic_type_tokens(f, tokens);
+                return;
+            }
             RmlxNode::Ident => PARAMETER_TOKEN,
             RmlxNode::Symbol => u32::MAX,
             RmlxNode::NsIdent => TYPE_TOKEN,
@@ -399,7 +457,7 @@ fn enum_variant_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>)
     });
 }
 
-//enum = { attribute_list* ~ ENUM ~ ident ~ LBRACE ~ enum_variant ~ (COMMA ~ enum_variant)* ~ COMMA? ~ RBRACE }
+//enum = { attribute_list* ~ ENUM ~ ident ~ generic_params? ~ LBRACE ~ enum_variant ~ (COMMA ~ enum_variant)* ~ COMMA? ~ RBRACE }
 fn enum_tokens(ancestor: &CstNode<RmlxNode>, cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
     cst.children.iter().for_each(|f| {
         let token_type = match f.kind {
@@ -411,6 +469,10 @@ fn enum_tokens(ancestor: &CstNode<RmlxNode>, cst: &CstNode<RmlxNode>, tokens: &m
                 enum_variant_tokens(f, tokens);
                 return;
             }
+            RmlxNode::GenericParams => {
+                generic_params_tokens(f, tokens);
+                return;
+            }
             RmlxNode::Keyword => KEYWORD_TOKEN,
             RmlxNode::Ident => TYPE_TOKEN,
             RmlxNode::Symbol => u32::MAX,
@@ -458,7 +520,7 @@ fn count_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
     });
 }
 
-//group_entry  = { PLUS ~ UNIQUE? ~ ns_ident ~ count }
+//group_entry  = { PLUS ~ ((ONE_OF ~ LPAREN ~ ns_ident ~ (COMMA ~ ns_ident)* ~ RPAREN) | (UNIQUE? ~ ns_ident ~ count?)) }
 fn group_entry_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
     let mut iter = cst.children.iter();
     let token = iter.next().unwrap();
@@ -475,6 +537,7 @@ fn group_entry_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>)
         let token_type = match f.kind {
             RmlxNode::Keyword => KEYWORD_TOKEN,
             RmlxNode::NsIdent => TYPE_TOKEN,
+            RmlxNode::Symbol => u32::MAX,
             RmlxNode::Count => {
                 count_tokens(f, tokens);
                 return;
@@ -525,7 +588,7 @@ fn group_content_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>
     });
 }
 
-//group = { attribute_list* ~ GROUP ~ ident ~ count* ~ (group_content | SEMI) }
+//group = { attribute_list* ~ GROUP ~ ident ~ ORDERED? ~ (group_content | SEMI) }
 fn group_tokens(ancestor: &CstNode<RmlxNode>, cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
     let mut iter = cst.children.iter();
 
@@ -614,7 +677,44 @@ fn expression_tokens(ancestor: &CstNode<RmlxNode>, cst: &CstNode<RmlxNode>, toke
     });
 }
 
-//custom_types = { enum | struct | element | extend_group | group | expression }
+//alias = { TYPE ~ ident ~ ASSIGN ~ type_ref ~ SEMI }
+//constant = { CONST ~ ident ~ COLON ~ type_ref ~ ASSIGN ~ default_value ~ SEMI }
+fn alias_tokens(ancestor: &CstNode<RmlxNode>, cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
+    let mut iter = cst.children.iter();
+    let token = iter.next().unwrap();
+    assert_eq!(token.kind, RmlxNode::Keyword);
+    tokens.push(SemanticToken {
+        delta_line: ancestor.delta_line,
+        delta_start: ancestor.delta_start,
+        length: token.text.len() as u32,
+        token_type: KEYWORD_TOKEN,
+        token_modifiers_bitset: 0,
+    });
+
+    iter.for_each(|f| {
+        let token_type = match f.kind {
+            RmlxNode::GenericType => {
+                generic_type_tokens(f, tokens);
+                return;
+            }
+            RmlxNode::Ident if cst.kind == RmlxNode::Constant => PARAMETER_TOKEN,
+            RmlxNode::Ident | RmlxNode::NsIdent => TYPE_TOKEN,
+            RmlxNode::DefaultValue => NUMBER_TOKEN,
+            RmlxNode::Symbol => u32::MAX,
+            _ => unreachable!(),
+        };
+
+        tokens.push(SemanticToken {
+            delta_line: f.delta_line,
+            delta_start: f.delta_start,
+            length: f.text.len() as u32,
+            token_type,
+            token_modifiers_bitset: 0,
+        });
+    });
+}
+
+//custom_types = { enum | struct | element | extend_group | group | expression | alias | constant }
 fn custom_type_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>) {
     let token = cst.children.first().unwrap();
     match token.kind {
@@ -623,6 +723,7 @@ fn custom_type_tokens(cst: &CstNode<RmlxNode>, tokens: &mut Vec<SemanticToken>)
         RmlxNode::Enum => enum_tokens(cst, token, tokens),
         RmlxNode::Expression => expression_tokens(cst, token, tokens),
         RmlxNode::Group => group_tokens(cst, token, tokens),
+        RmlxNode::Alias | RmlxNode::Constant => alias_tokens(cst, token, tokens),
         _ => unreachable!(),
     }
 }
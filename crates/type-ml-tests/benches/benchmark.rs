@@ -1,5 +1,6 @@
 use divan::Bencher;
 use lexer_core::CstNode;
+use std::fmt::Write;
 use type_ml::{LayoutModel, RmlParser};
 use type_ml_definitions::{AnalysisWorkspace, RmlxNode, RmlxParser, Rule, build_schema_ast};
 use url::Url;
@@ -10,12 +11,10 @@ fn main() {
 
 #[divan::bench]
 fn parse_ast(bench: Bencher) {
+    const CONTENT: &str = include_str!(concat!(env!("CARGO_WORKSPACE_DIR"), "examples/schema.tmd"));
     bench
-        .with_inputs(|| {
-            const CONTENT: &str = include_str!(concat!(env!("CARGO_WORKSPACE_DIR"), "examples/schema.tmd"));
-            CstNode::new::<RmlxParser>(CONTENT, Rule::file).unwrap()
-        })
-        .bench_values(|cst| build_schema_ast(&cst));
+        .with_inputs(|| CstNode::new::<RmlxParser>(CONTENT, Rule::file).unwrap())
+        .bench_values(|cst| build_schema_ast(&cst, CONTENT));
 }
 
 #[divan::bench]
@@ -49,3 +48,49 @@ fn rml_full_analysis(bench: Bencher) {
             LayoutModel::validate(ast, PATH).unwrap();
         });
 }
+
+/// Builds a layout with `entities` entities, each carrying a node and a background color.
+fn large_layout(entities: usize) -> String {
+    let mut layout = String::from("#use <schema.tmd>\n<base::Layout>\n");
+    for _ in 0..entities {
+        layout.push_str(
+            "<base::Entity>\n<Node width=30px height=40px/>\n<BackgroundColor self=#FFFFFF/>\n</base::Entity>\n",
+        );
+    }
+    layout.push_str("</base::Layout>\n");
+    layout
+}
+
+#[divan::bench(args = [10, 100, 250], sample_count = 20)]
+fn rml_large_layout_analysis(bench: Bencher, entities: usize) {
+    const PATH: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/layout.tml");
+    let content = large_layout(entities);
+    bench
+        .with_inputs(|| RmlParser::build_ast(&content).unwrap())
+        .bench_values(|ast| {
+            LayoutModel::validate(ast, PATH).unwrap();
+        });
+}
+
+/// Writes a schema declaring `symbols` structs to a temporary file.
+fn large_schema(symbols: usize) -> Url {
+    let mut schema = String::from("group Root;\nelement Layout -> Root;\n");
+    for i in 0..symbols {
+        writeln!(schema, "struct Struct{i} {{\n    value: f32,\n}}").unwrap();
+    }
+
+    let path = std::env::temp_dir().join(format!("type-ml-bench-{symbols}.tmd"));
+    std::fs::write(&path, schema).unwrap();
+    Url::from_file_path(path).unwrap()
+}
+
+#[divan::bench(args = [10, 100, 1000])]
+fn type_lookup(bench: Bencher, symbols: usize) {
+    let model = AnalysisWorkspace::new(large_schema(symbols)).run().unwrap();
+    let names = (0..symbols).map(|i| format!("Struct{i}")).collect::<Vec<_>>();
+    bench.bench(|| {
+        for name in &names {
+            assert!(model.get_type_by_name(0, divan::black_box(name)).unwrap().is_some());
+        }
+    });
+}
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use type_ml::{LayoutAst, LayoutModel, RmlParser};
+    use std::path::{Path, PathBuf};
+    use type_ml::{DependencyGraph, LayoutAst, LayoutModel, ResolvedBaseType, ResolvedType, RmlParser};
+    use type_ml_definitions::{AnalysisWorkspace, Symbol, SymbolKind, doc_at};
+    use url::Url;
 
     fn load(path: &str) -> Result<LayoutAst, type_ml_definitions::Error> {
         let content = std::fs::read_to_string(path).unwrap();
@@ -16,6 +19,387 @@ mod tests {
         assert!(result.is_ok(), "{}", result.unwrap_err());
     }
 
+    #[test]
+    fn resolve() {
+        const PATH: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/layout.tml");
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::resolve(ast.unwrap(), PATH);
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+        let root = result.unwrap();
+        assert_eq!(root.namespace(), Some("base"));
+        assert_eq!(root.identifier(), "Layout");
+        assert_eq!(root.children().len(), 3);
+
+        let width = root.children()[0].attributes()[0].value();
+        let ResolvedType::Enum(width) = width else {
+            panic!("Expected an enum value, found {width:?}");
+        };
+        assert_eq!(width.identifier(), "Percent");
+        assert!(matches!(
+            width.payload(),
+            Some(ResolvedType::Base(ResolvedBaseType::F32(100.0)))
+        ));
+
+        let display = root.children()[0]
+            .attributes()
+            .iter()
+            .find(|a| a.identifier() == "display")
+            .expect("Default value is not applied");
+        assert!(matches!(display.value(), ResolvedType::Enum(v) if v.identifier() == "Flex"));
+    }
+
+    #[test]
+    fn dependency_graph() {
+        const LAYOUT: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/layout.tml");
+        const SCHEMA: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/schema.tmd");
+        const BASE: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/base.tmd");
+        let mut graph = DependencyGraph::default();
+        graph.update(Path::new(LAYOUT));
+        assert_eq!(graph.dependencies(Path::new(LAYOUT)), [PathBuf::from(SCHEMA)]);
+        assert_eq!(graph.dependencies(Path::new(SCHEMA)), [PathBuf::from(BASE)]);
+        assert_eq!(graph.affected_layouts(&[PathBuf::from(BASE)]), [PathBuf::from(LAYOUT)]);
+        assert!(graph.affected_layouts(&[PathBuf::from("unknown.tmd")]).is_empty());
+    }
+
+    #[test]
+    fn generics() {
+        const LAYOUT: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/generics/layout.tml");
+        const SCHEMA: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/generics/definitions.tmd");
+        let ast = load(LAYOUT);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), LAYOUT);
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+
+        let model = AnalysisWorkspace::new(Url::from_file_path(SCHEMA).unwrap()).run();
+        assert!(model.is_ok(), "{}", model.unwrap_err());
+        let model = model.unwrap();
+        for identifier in [
+            "Pair<u32, u32>",
+            "Pair<String, items::Item>",
+            "Entry<items::Item>",
+            "Option<Array<f32>>",
+            "Either<f32, String>",
+        ] {
+            let count = model.modules()[0]
+                .iter()
+                .filter(|symbol| symbol.identifier() == identifier)
+                .count();
+            assert_eq!(count, 1, "{identifier}");
+        }
+    }
+
+    #[test]
+    fn option() {
+        const PATH: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/option/layout.tml");
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::resolve(ast.unwrap(), PATH);
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+        let root = result.unwrap();
+        let attribute = |name: &str| {
+            root.attributes()
+                .iter()
+                .find(|a| a.identifier() == name)
+                .unwrap_or_else(|| panic!("Missing attribute {name}"))
+                .value()
+        };
+
+        assert!(matches!(
+            attribute("scale"),
+            ResolvedType::Option(Some(value)) if matches!(**value, ResolvedType::Base(ResolvedBaseType::F32(1.5)))
+        ));
+        assert!(matches!(attribute("fit"), ResolvedType::Option(None)));
+        assert!(matches!(attribute("opacity"), ResolvedType::Option(None)));
+        assert!(
+            matches!(attribute("tags"), ResolvedType::Option(Some(value)) if matches!(&**value, ResolvedType::List(v) if v.len() == 2))
+        );
+
+        let ResolvedType::Option(Some(size)) = attribute("size") else {
+            panic!("Expected an optional struct");
+        };
+        let ResolvedType::Struct(size) = &**size else {
+            panic!("Expected a struct value, found {size:?}");
+        };
+        let ratio = size.fields().iter().find(|f| f.identifier() == "ratio");
+        assert!(matches!(ratio.map(|f| f.value()), Some(ResolvedType::Option(None))));
+    }
+
+    #[test]
+    fn map() {
+        const PATH: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/map/layout.tml");
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::resolve(ast.unwrap(), PATH);
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+        let root = result.unwrap();
+
+        let colors = root.attributes()[0].value();
+        let ResolvedType::Map(entries) = colors else {
+            panic!("Expected a map value, found {colors:?}");
+        };
+        let keys = entries
+            .iter()
+            .map(|e| match e.key() {
+                ResolvedType::Base(ResolvedBaseType::String(key)) => key.as_str(),
+                key => panic!("Expected a string key, found {key:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["hover", "pressed", "a: b"]);
+        assert!(matches!(entries[0].value(), ResolvedType::Enum(v) if v.identifier() == "Red"));
+
+        let labels = root.attributes()[1].value();
+        assert!(
+            matches!(labels, ResolvedType::Option(Some(map)) if matches!(map.as_ref(), ResolvedType::Map(e) if e.is_empty())),
+            "{labels:?}"
+        );
+        let ResolvedType::Struct(palette) = root.attributes()[2].value() else {
+            panic!("Expected a struct value");
+        };
+        let overrides = palette.fields().iter().find(|f| f.identifier() == "overrides").unwrap();
+        assert!(matches!(overrides.value(), ResolvedType::Map(e) if e.is_empty()));
+
+        //The schema's own `Color` replaces the built-in one, even in a struct resolved before the enum.
+        let colors = palette.fields().iter().find(|f| f.identifier() == "colors").unwrap();
+        assert!(
+            matches!(colors.value(), ResolvedType::Map(e) if matches!(e[0].value(), ResolvedType::Enum(v) if v.identifier() == "Green")),
+            "{:?}",
+            colors.value()
+        );
+    }
+
+    #[test]
+    fn constant_scope() {
+        const PATH: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/constant_scope/layout.tml");
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::resolve(ast.unwrap(), PATH);
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+        let root = result.unwrap();
+        let attribute = |name: &str| {
+            root.attributes()
+                .iter()
+                .find(|a| a.identifier() == name)
+                .unwrap_or_else(|| panic!("Missing attribute {name}"))
+                .value()
+        };
+
+        //`Auto` is an `f32` constant, so the variant is used.
+        assert!(matches!(attribute("width"), ResolvedType::Enum(v) if v.identifier() == "Auto"));
+        //`other::Fill` isn't visible without its namespace.
+        assert!(matches!(attribute("height"), ResolvedType::Enum(v) if v.identifier() == "Fill"));
+        //The default in `other` sees its own constant.
+        let ResolvedType::Struct(gap) = attribute("gap") else {
+            panic!("Expected a struct value");
+        };
+        assert!(matches!(
+            gap.fields()[0].value(),
+            ResolvedType::Base(ResolvedBaseType::F32(1.0))
+        ));
+    }
+
+    #[test]
+    fn primitives() {
+        const PATH: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/primitives/layout.tml");
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::resolve(ast.unwrap(), PATH);
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+        let root = result.unwrap();
+        let attribute = |name: &str| {
+            let value = root
+                .attributes()
+                .iter()
+                .find(|a| a.identifier() == name)
+                .unwrap_or_else(|| panic!("Missing attribute {name}"))
+                .value();
+            let ResolvedType::Base(value) = value else {
+                panic!("Expected a primitive value, found {value:?}");
+            };
+            value
+        };
+
+        assert!(matches!(attribute("separator"), ResolvedBaseType::Char('|')));
+        assert!(matches!(attribute("id"), ResolvedBaseType::U128(u128::MAX)));
+        assert!(matches!(attribute("offset"), ResolvedBaseType::I128(i128::MIN)));
+        assert!(matches!(attribute("index"), ResolvedBaseType::Usize(42)));
+        assert!(matches!(attribute("delta"), ResolvedBaseType::Isize(-1)));
+        for (name, expected) in [
+            ("background", "#ff000080"),
+            ("border", "#0080ff"),
+            ("outline", "#008000"),
+        ] {
+            assert!(
+                matches!(attribute(name), ResolvedBaseType::Color(c) if c.to_string() == expected),
+                "{name}"
+            );
+        }
+        assert!(matches!(attribute("icon"), ResolvedBaseType::Path(p) if p == Path::new("icons/close.png")));
+        assert!(matches!(attribute("link"), ResolvedBaseType::Url(u) if u.host_str() == Some("example.com")));
+    }
+
+    #[test]
+    fn inheritance() {
+        const PATH: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/inheritance/layout.tml");
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::resolve(ast.unwrap(), PATH);
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+        let root = result.unwrap();
+        let attribute = |name: &str| {
+            root.attributes()
+                .iter()
+                .find(|a| a.identifier() == name)
+                .unwrap_or_else(|| panic!("Missing attribute {name}"))
+                .value()
+        };
+
+        assert!(matches!(
+            attribute("left"),
+            ResolvedType::Base(ResolvedBaseType::F32(4.0))
+        ));
+        assert!(matches!(
+            attribute("top"),
+            ResolvedType::Base(ResolvedBaseType::F32(8.0))
+        ));
+        let ResolvedType::Struct(border) = attribute("border") else {
+            panic!("Expected a struct value");
+        };
+        let fields = border.fields().iter().map(|f| f.identifier()).collect::<Vec<_>>();
+        assert_eq!(fields, ["left", "color", "right", "top", "bottom"]);
+
+        //`..base::Rect` is another struct than the `Rect` that spreads it.
+        let ResolvedType::Struct(frame) = attribute("frame") else {
+            panic!("Expected a struct value");
+        };
+        let fields = frame.fields().iter().map(|f| f.identifier()).collect::<Vec<_>>();
+        assert_eq!(fields, ["width", "depth", "height"]);
+    }
+
+    #[test]
+    fn constants() {
+        const PATH: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/constants/layout.tml");
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::resolve(ast.unwrap(), PATH);
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+        let root = result.unwrap();
+        let attribute = |name: &str| {
+            root.attributes()
+                .iter()
+                .find(|a| a.identifier() == name)
+                .unwrap_or_else(|| panic!("Missing attribute {name}"))
+                .value()
+        };
+        let px = |value: &ResolvedType| match value {
+            ResolvedType::Enum(variant) if variant.identifier() == "Px" => match variant.payload() {
+                Some(ResolvedType::Base(ResolvedBaseType::F32(px))) => *px,
+                other => panic!("Unexpected payload {other:?}"),
+            },
+            other => panic!("Expected Px, found {other:?}"),
+        };
+
+        assert!(matches!(
+            attribute("opacity"),
+            ResolvedType::Base(ResolvedBaseType::F32(0.5))
+        ));
+        let ResolvedType::Option(Some(width)) = attribute("width") else {
+            panic!("Expected Some");
+        };
+        assert_eq!(px(width), 8.0);
+
+        let ResolvedType::Struct(padding) = attribute("padding") else {
+            panic!("Expected a struct value");
+        };
+        let fields = padding.fields().iter().map(|f| px(f.value())).collect::<Vec<_>>();
+        assert_eq!(fields, [8.0, 2.0]);
+    }
+
+    #[test]
+    fn docs() {
+        const LAYOUT: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/docs/layout.tml");
+        const SCHEMA: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/docs/definitions.tmd");
+        let ast = load(LAYOUT);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), LAYOUT);
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+
+        let model = AnalysisWorkspace::new(Url::from_file_path(SCHEMA).unwrap()).run();
+        assert!(model.is_ok(), "{}", model.unwrap_err());
+        let model = model.unwrap();
+        let symbol = |name: &str| {
+            model
+                .get_type_by_name(0, name)
+                .unwrap()
+                .unwrap_or_else(|| panic!("Missing symbol {name}"))
+        };
+
+        assert_eq!(symbol("Root").doc(), Some("Top-level elements."));
+        assert_eq!(symbol("Content").doc(), Some("What a panel holds."));
+        assert_eq!(symbol("Spacing").doc(), Some("Space around the content."));
+        assert_eq!(symbol("GAP").doc(), Some("The default gap between children."));
+        assert_eq!(symbol("Insets").doc(), None);
+
+        let SymbolKind::Enum(direction) = symbol("Direction") else {
+            panic!("Expected an enum");
+        };
+        assert_eq!(direction.doc(), Some("How a panel is laid out."));
+        let variants = direction.variants.iter().map(|v| v.doc.as_deref()).collect::<Vec<_>>();
+        assert_eq!(variants, [Some("Children are placed side by side."), None]);
+
+        let SymbolKind::Struct(insets) = symbol("Insets") else {
+            panic!("Expected a struct");
+        };
+        assert_eq!(insets.fields[0].doc(), Some("Space on the left,\nin pixels."));
+        assert_eq!(insets.fields[1].doc(), None);
+
+        let SymbolKind::Element(panel) = symbol("Panel") else {
+            panic!("Expected an element");
+        };
+        assert_eq!(panel.doc(), Some("A box that lays out its children."));
+        let direction = panel.fields().iter().find(|f| f.identifier() == "direction").unwrap();
+        assert_eq!(direction.doc(), Some("The main axis."));
+        assert!(direction.is_required());
+    }
+
+    #[test]
+    fn hover() {
+        const SCHEMA: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/docs/definitions.tmd");
+        let path = Url::from_file_path(SCHEMA).unwrap();
+        let doc = |source: &str, word: &str, nth: usize| {
+            let offset = source.match_indices(word).nth(nth).unwrap().0;
+            doc_at(source, &path, offset)
+        };
+
+        let source = "\
+            struct A {\n    /// A's left.\n    left: f32,\n}\n\
+            /// B.\nstruct B {\n    /// B's left.\n    left: f32,\n    /// The field.\n    A: A,\n}\n";
+        assert_eq!(doc(source, "left:", 1).as_deref(), Some("B's left."));
+        assert_eq!(doc(source, "A: A", 0).as_deref(), Some("The field."));
+        assert_eq!(doc(source, "B {", 0).as_deref(), Some("B."));
+        assert_eq!(doc(source, "A,", 0), None);
+
+        let source = std::fs::read_to_string(SCHEMA).unwrap();
+        assert_eq!(
+            doc(&source, "Row", 0).as_deref(),
+            Some("Children are placed side by side.")
+        );
+        assert_eq!(doc(&source, "Insets;", 0), None);
+        assert_eq!(
+            doc(&source, "Spacing,", 0).as_deref(),
+            Some("Space around the content.")
+        );
+        assert_eq!(
+            doc(&source, "Align>", 0).as_deref(),
+            Some("Where children are placed along the cross axis.")
+        );
+        assert_eq!(
+            doc(&source, "base::", 0).as_deref(),
+            Some("Where children are placed along the cross axis.")
+        );
+        assert_eq!(doc(&source, "Content\n}", 0).as_deref(), Some("What a panel holds."));
+    }
+
     #[test]
     fn unresolved_type() {
         const PATH: &str = concat!(
@@ -232,6 +616,24 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn first_count_violation() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/51_first_count_violation/layout.tml"
+        );
+        // Every group but Content is missing; the first one declared is reported
+        for _ in 0..8 {
+            let ast = load(PATH);
+            assert!(ast.is_ok(), "{}", ast.unwrap_err());
+            let result = LayoutModel::validate(ast.unwrap(), PATH);
+            assert!(matches!(
+                result,
+                Err(type_ml_definitions::Error::InsufficientElements { group, actual: 0, .. }) if group == "Header"
+            ));
+        }
+    }
+
     #[test]
     fn insufficient_elements() {
         const PATH: &str = concat!(
@@ -304,7 +706,658 @@ mod tests {
         ));
     }
 
-    //TODO Incorrect pattern
+    #[test]
+    fn incorrect_pattern() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/19_incorrect_pattern/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::IncorrectPattern { line: 4, column: 5, .. }
+        ));
+    }
+
+    #[test]
+    fn pattern_mismatch() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/20_pattern_mismatch/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::InvalidArgumentType(_, _)
+        ));
+    }
+
+    #[test]
+    fn invalid_default_value() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/21_invalid_default_value/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::InvalidDefaultValue { .. }
+        ));
+    }
+
+    #[test]
+    fn structured_default() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/50_structured_default/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        let type_ml_definitions::Error::InvalidDefaultValue { field, source } = result.unwrap_err() else {
+            panic!("Expected InvalidDefaultValue");
+        };
+        assert_eq!(field, "Label::tags");
+        assert!(matches!(*source, type_ml_definitions::Error::StructuredValue(_)));
+    }
+
+    #[test]
+    fn misplaced_range() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/52_misplaced_range/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::MisplacedAnnotation { annotation, field, ty }
+                if annotation == "range" && field == "Label::name" && ty == "String"
+        ));
+    }
+
+    #[test]
+    fn misplaced_len() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/53_misplaced_len/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::MisplacedAnnotation { annotation, field, ty }
+                if annotation == "len" && field == "Insets::left" && ty == "Val"
+        ));
+    }
+
+    #[test]
+    fn variant_pattern_arguments() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/54_variant_pattern_arguments/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::InvalidAnnotationValue { annotation, value }
+                if annotation == "pattern" && value == "[0-9]+px"
+        ));
+    }
+
+    #[test]
+    fn groups_string() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/55_groups_string/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::InvalidAnnotationValue { annotation, value }
+                if annotation == "groups" && value == "Root"
+        ));
+    }
+
+    #[test]
+    fn missing_required_attribute() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/22_missing_required_attribute/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::MissingRequiredField(field) if field == "title"
+        ));
+    }
+
+    #[test]
+    fn duplicate_attribute() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/23_duplicate_attribute/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::DuplicateField(field) if field == "width"
+        ));
+    }
+
+    #[test]
+    fn struct_field_not_found() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/24_struct_field_not_found/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::FieldNotFound(field) if field == "radius"
+        ));
+    }
+
+    #[test]
+    fn duplicate_struct_field() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/25_duplicate_struct_field/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::DuplicateField(field) if field == "color"
+        ));
+    }
+
+    #[test]
+    fn missing_struct_field() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/26_missing_struct_field/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::MissingRequiredField(field) if field == "height"
+        ));
+    }
+
+    #[test]
+    fn struct_impl_type_mismatch() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/27_struct_impl_type_mismatch/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::StructImplTypeMismatch { name, .. } if name == "WindowSize"
+        ));
+    }
+
+    #[test]
+    fn struct_not_found() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/28_struct_not_found/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::StructNotFound(name) if name == "Window"
+        ));
+    }
+
+    #[test]
+    fn impl_not_found() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/29_impl_not_found/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::ImplNotFound(name) if name == "WindowTitel"
+        ));
+    }
+
+    #[test]
+    fn impl_kind_mismatch() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/30_impl_kind_mismatch/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::ImplKindMismatch { name, .. } if name == "WindowTitle"
+        ));
+    }
+
+    #[test]
+    fn duplicate_impl() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/31_duplicate_impl/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let ast = ast.unwrap();
+        let unused = ast
+            .unused_impls()
+            .iter()
+            .map(|i| i.identifier.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(unused, ["UnusedTitle"]);
+        let result = LayoutModel::validate(ast, PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::DuplicateImpl(name) if name == "WindowSize"
+        ));
+    }
+
+    #[test]
+    fn element_not_allowed_in_group() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/32_element_not_allowed_in_group/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                type_ml_definitions::Error::ElementNotAllowedInGroup { element, allowed, .. }
+                    if element == "Window" && allowed.len() == 1 && allowed[0] == "Widget"
+            ),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn root_element_not_found() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/33_root_element_not_found/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::RootElementNotFound
+        ));
+    }
+
+    #[test]
+    fn mismatched_closing_tag() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/34_mismatched_closing_tag/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(matches!(
+            ast.unwrap_err(),
+            type_ml_definitions::Error::MismatchedClosingTag { expected, found } if expected == "Window" && found == "Text"
+        ));
+    }
+
+    #[test]
+    fn expression_outside_restricted_group() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/35_expression_outside_restricted_group/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                type_ml_definitions::Error::ExpressionOutsideRestrictedGroup { expression, groups }
+                    if expression == "Item" && groups.len() == 1 && groups[0] == "Template"
+            ),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn expression_inside_restricted_group() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/35_expression_outside_restricted_group/allowed.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+    }
+
+    #[test]
+    fn missing_required_child() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/36_missing_required_child/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::InsufficientElements { actual: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn excessive_range() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/37_excessive_range/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        // The upper bound is inclusive, so only the fifth widget is one too many.
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::ExcessiveElements { actual: 5, .. }
+        ));
+    }
+
+    #[test]
+    fn element_out_of_order() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/38_element_out_of_order/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                type_ml_definitions::Error::ElementOutOfOrder { element, group, after }
+                    if element == "Paragraph" && group == "Body" && after == "Footer"
+            ),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn ordered() {
+        const PATH: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/ordered/layout.tml");
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+    }
+
+    #[test]
+    fn choices() {
+        const PATH: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "examples/choices/layout.tml");
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+    }
+
+    #[test]
+    fn choice_not_satisfied() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/39_choice_not_satisfied/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                type_ml_definitions::Error::ChoiceNotSatisfied { alternatives, actual: 2 } if alternatives.len() == 2
+            ),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn invalid_length() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/40_invalid_length/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::InvalidLength { expected, actual: 3 } if expected.to_string() == "1..2"
+        ));
+    }
+
+    #[test]
+    fn duplicate_key() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/41_duplicate_key/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::DuplicateKey(key) if key == "\"hover\""
+        ));
+    }
+
+    #[test]
+    fn invalid_map_key() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/42_invalid_map_key/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::InvalidMapKey { key, .. } if key == "u32"
+        ));
+    }
+
+    #[test]
+    fn invalid_color() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/43_invalid_color/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::InvalidArgumentType(value, ty) if value == "#ff00" && ty == "Color"
+        ));
+    }
+
+    #[test]
+    fn out_of_range() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/44_out_of_range/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::ConstraintViolation { constraint, value } if constraint == "range(0, )" && value == "-4"
+        ));
+    }
+
+    #[test]
+    fn string_pattern_mismatch() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/45_string_pattern_mismatch/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::ConstraintViolation { constraint, value }
+                if constraint == "pattern \"[a-z][a-z0-9_]*\"" && value == "\"User\""
+        ));
+    }
+
+    #[test]
+    fn inheritance_cycle() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/46_inheritance_cycle/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::InheritanceCycle(cycle) if cycle == "A -> C -> B -> A"
+        ));
+    }
+
+    #[test]
+    fn cross_file_cycle() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/56_cross_file_cycle/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::InheritanceCycle(cycle) if cycle == "base::Frame -> Rect -> base::Frame"
+        ));
+    }
+
+    #[test]
+    fn conflicting_field() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/47_conflicting_field/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::ConflictingField { field, .. } if field == "x"
+        ));
+    }
+
+    #[test]
+    fn invalid_constant() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/48_invalid_constant/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::InvalidConstant { constant, .. } if constant == "OPACITY"
+        ));
+    }
+
+    #[test]
+    fn constant_type_mismatch() {
+        const PATH: &str = concat!(
+            env!("CARGO_WORKSPACE_DIR"),
+            "examples/errors/49_constant_type_mismatch/layout.tml"
+        );
+        let ast = load(PATH);
+        assert!(ast.is_ok(), "{}", ast.unwrap_err());
+        let result = LayoutModel::validate(ast.unwrap(), PATH);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            type_ml_definitions::Error::ConstantTypeMismatch { constant, .. } if constant == "COUNT"
+        ));
+    }
+
     //TODO Load error
     //TODO Url error
     //TODO Type is not parsable
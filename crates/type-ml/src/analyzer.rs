@@ -1,7 +1,7 @@
-use crate::unresolved::Expression;
+use crate::unresolved::{ArgumentValue, Expression, FieldValue, List, Struct, StructImpl, full_path};
 use std::collections::{HashMap, HashSet};
-use type_ml_definitions::{Count, CountEquality, ExpressionField, ExpressionSymbol, Symbol};
-use type_ml_definitions::{GroupConfig, SchemaModel, SymbolRef};
+use type_ml_definitions::{Count, CountEquality, ExpressionField, ExpressionSymbol, Length, Symbol};
+use type_ml_definitions::{GroupConfig, MapSymbol, SchemaModel, SymbolKind, SymbolRef};
 
 pub struct AnalyzerState {
     group: SymbolRef,
@@ -14,8 +14,14 @@ pub struct PreviousElement {
     group: SymbolRef,
     state: usize,
     uniques: HashSet<SymbolRef>,
-    constraints: HashMap<SymbolRef, Count>,
+    constraints: Vec<(SymbolRef, Count)>,
     counter: HashMap<SymbolRef, HashMap<(Option<String>, String), u32>>,
+    attributes: HashSet<String>,
+    choices: Vec<Vec<SymbolRef>>,
+    /// Entries of an ordered group, in declaration order.
+    order: Option<Vec<SymbolRef>>,
+    /// Index in `order` of the entry the last child belonged to.
+    position: usize,
 }
 
 pub struct RmlAnalyzer {
@@ -92,6 +98,10 @@ impl RmlAnalyzer {
         }
     }
 
+    pub(crate) const fn model(&self) -> &SchemaModel {
+        &self.model
+    }
+
     pub fn is_allowed_element(&self, namespace: Option<&str>, name: &str) -> Result<bool, type_ml_definitions::Error> {
         let namespace_id = self.model.get_namespace_id(namespace)?;
         let element = self
@@ -108,6 +118,26 @@ impl RmlAnalyzer {
         Ok(groups.iter().any(|g| g.symbol() == bind_group))
     }
 
+    /// Builds the error for an element that [`Self::is_allowed_element`] rejected.
+    #[must_use]
+    pub fn element_not_allowed(&self, namespace: Option<&str>, name: &str) -> type_ml_definitions::Error {
+        let group_ref = self.states[self.active].group;
+        let group = self
+            .model
+            .get_type_by_ref(group_ref)
+            .as_group_symbol()
+            .expect("Unreachable!");
+        type_ml_definitions::Error::ElementNotAllowedInGroup {
+            element: full_path(namespace, name),
+            group: self.get_type_full_path(group_ref),
+            allowed: group
+                .groups()
+                .iter()
+                .map(|g| self.get_type_full_path(g.symbol()))
+                .collect(),
+        }
+    }
+
     pub fn enter_element(&mut self, namespace: Option<&str>, name: &str) -> Result<(), type_ml_definitions::Error> {
         debug_assert!(self.is_allowed_element(namespace, name)?);
 
@@ -119,6 +149,8 @@ impl RmlAnalyzer {
             .expect("Unreachable!");
         let bind_group = element.group();
 
+        self.check_order(namespace, name, bind_group)?;
+
         let ty = self.model.get_type_by_ref(bind_group).unwrap().expect("Unreachable!");
         let group = ty.as_group_symbol();
         if group.groups().is_empty() {
@@ -130,6 +162,10 @@ impl RmlAnalyzer {
                 counter: HashMap::default(),
                 constraints: group.get_constraints(),
                 uniques: group.get_unique_groups(),
+                attributes: HashSet::default(),
+                choices: Vec::default(),
+                order: None,
+                position: 0,
             });
             return Ok(());
         }
@@ -152,47 +188,94 @@ impl RmlAnalyzer {
             counter: HashMap::default(),
             constraints: group.get_constraints(),
             uniques: group.get_unique_groups(),
+            attributes: HashSet::default(),
+            choices: group.get_choices(),
+            order: group
+                .ordered()
+                .then(|| group.groups().iter().map(GroupConfig::symbol).collect()),
+            position: 0,
         });
         self.active = next;
 
         Ok(())
     }
 
-    fn get_group_full_path(&self, group: SymbolRef) -> String {
-        let group_kind = self.model.get_type_by_ref(group).unwrap().expect("Unreachable!");
-        let namespace = &self.model.namespaces[group.namespace];
-        format!("{namespace}::{}", group_kind.identifier())
-    }
-
-    fn check_and_change_counter(
+    /// Checks that a child of an ordered group doesn't come before the children of an earlier entry.
+    fn check_order(
         &mut self,
-        group: SymbolRef,
         namespace: Option<&str>,
         name: &str,
+        bind_group: SymbolRef,
     ) -> Result<(), type_ml_definitions::Error> {
+        let Some(parent) = self.depth.last() else {
+            return Ok(());
+        };
+        let Some(order) = &parent.order else {
+            return Ok(());
+        };
+
+        let position = order.iter().position(|g| *g == bind_group).expect("Unreachable!");
+        if position < parent.position {
+            return Err(type_ml_definitions::Error::ElementOutOfOrder {
+                element: full_path(namespace, name),
+                group: self.get_type_full_path(bind_group),
+                after: self.get_type_full_path(order[parent.position]),
+            });
+        }
+
+        self.depth.last_mut().expect("Unreachable!").position = position;
+        Ok(())
+    }
+
+    fn get_type_full_path(&self, ty: SymbolRef) -> String {
+        self.model.type_path(ty)
+    }
+
+    fn change_counter(&mut self, group: SymbolRef, namespace: Option<&str>, name: &str) {
         if let Some(last) = self.depth.last_mut() {
             let counter = last.counter.entry(group).or_default();
-            let actual_count = counter
+            *counter
                 .entry((namespace.map(str::to_string), name.to_string()))
-                .or_default();
-            *actual_count += 1;
-
-            let actual_count = *actual_count;
-            let count = *last.constraints.get(&group).unwrap();
-            let result = count.in_range(actual_count);
-            return match result {
-                CountEquality::More => Err(type_ml_definitions::Error::ExcessiveElements {
-                    group: self.get_group_full_path(group),
-                    actual: actual_count,
-                    expected: count,
-                }),
-                CountEquality::Less => Err(type_ml_definitions::Error::InsufficientElements {
-                    group: self.get_group_full_path(group),
-                    actual: actual_count,
-                    expected: count,
-                }),
-                CountEquality::Ok => Ok(()),
-            };
+                .or_default() += 1;
+        }
+    }
+
+    /// Checks the number of children from each group once all of them have been seen.
+    fn check_counters(&self) -> Result<(), type_ml_definitions::Error> {
+        let last = self.depth.last().expect("Unreachable!");
+        for (group, count) in &last.constraints {
+            let actual = last.counter.get(group).map_or(0, |elements| elements.values().sum());
+            match count.in_range(actual) {
+                CountEquality::More => {
+                    return Err(type_ml_definitions::Error::ExcessiveElements {
+                        group: self.get_type_full_path(*group),
+                        actual,
+                        expected: *count,
+                    });
+                }
+                CountEquality::Less => {
+                    return Err(type_ml_definitions::Error::InsufficientElements {
+                        group: self.get_type_full_path(*group),
+                        actual,
+                        expected: *count,
+                    });
+                }
+                CountEquality::Ok => {}
+            }
+        }
+
+        for alternatives in &last.choices {
+            let actual = alternatives
+                .iter()
+                .filter_map(|group| last.counter.get(group))
+                .flat_map(HashMap::values)
+                .sum();
+            if actual != 1 {
+                return Err(type_ml_definitions::Error::ChoiceNotSatisfied {
+                    alternatives: alternatives.iter().map(|g| self.get_type_full_path(*g)).collect(),
+                    actual,
+                });
+            }
         }
 
         Ok(())
@@ -203,8 +286,10 @@ impl RmlAnalyzer {
             last.uniques.iter().try_for_each(|group| {
                 if let Some(elements) = last.counter.get(group) {
                     if let Some(((ns, ident), _)) = elements.iter().find(|((_, _), count)| **count > 1) {
-                        let full_path = format!("{}::{}", ns.clone().unwrap_or_default(), ident);
-                        Err(type_ml_definitions::Error::NotUniqueElement(full_path))
+                        Err(type_ml_definitions::Error::NotUniqueElement(full_path(
+                            ns.as_deref(),
+                            ident,
+                        )))
                     } else {
                         Ok::<(), type_ml_definitions::Error>(())
                     }
@@ -217,14 +302,46 @@ impl RmlAnalyzer {
         Ok(())
     }
 
+    fn check_required_attributes(&self) -> Result<(), type_ml_definitions::Error> {
+        let last_element = self.depth.last().expect("Unreachable!");
+        let element_namespace = self.model.get_namespace_id(last_element.namespace.as_deref())?;
+        let element = self
+            .model
+            .get_type_by_name(element_namespace, &last_element.name)
+            .as_element_symbol()
+            .expect("Unreachable!");
+
+        if let Some(field) = element.fields().iter().find(|field| {
+            field.is_required() && field.default().is_none() && !last_element.attributes.contains(field.identifier())
+        }) {
+            return Err(type_ml_definitions::Error::MissingRequiredField(
+                field.identifier().to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn exit_element(&mut self, namespace: Option<&str>, name: &str) -> Result<(), type_ml_definitions::Error> {
+        self.check_required_attributes()?;
+        self.check_counters()?;
         let previous_element = self.depth.pop().expect("Unreachable!");
-        assert!(previous_element.name == name && previous_element.namespace.as_deref() == namespace);
+        debug_assert!(previous_element.name == name && previous_element.namespace.as_deref() == namespace);
         self.active = previous_element.state;
-        self.check_and_change_counter(previous_element.group, namespace, name)?;
+        self.change_counter(previous_element.group, namespace, name);
         self.check_elements_uniqueness()
     }
 
+    /// Marks the attribute as present on the current element.
+    pub fn add_attribute(&mut self, name: &str) -> Result<(), type_ml_definitions::Error> {
+        let last_element = self.depth.last_mut().expect("Unreachable!");
+        if !last_element.attributes.insert(name.to_string()) {
+            return Err(type_ml_definitions::Error::DuplicateField(name.to_string()));
+        }
+
+        Ok(())
+    }
+
     pub fn is_valid_attribute(&self, name: &str, value: &str) -> Result<(), type_ml_definitions::Error> {
         let last_element = self.depth.last().expect("Unreachable!");
         let element_namespace = self.model.get_namespace_id(last_element.namespace.as_deref())?;
@@ -234,9 +351,233 @@ impl RmlAnalyzer {
             .as_element_symbol()
             .expect("Unreachable!");
         let field = element.field(name)?;
+        let value = self.model.expand_constant(value, field.ty(), element_namespace)?;
         let field_type = self.model.get_type_by_ref(field.ty());
         let field_type = field_type.as_ref();
-        field_type.can_parse(value, &self.model)
+        field_type.can_parse(value, &self.model)?;
+        field.constraints().check(value)
+    }
+
+    pub fn is_valid_struct(&self, name: &str, value: &Struct) -> Result<(), type_ml_definitions::Error> {
+        let last_element = self.depth.last().expect("Unreachable!");
+        let element_namespace = self.model.get_namespace_id(last_element.namespace.as_deref())?;
+        let element = self
+            .model
+            .get_type_by_name(element_namespace, &last_element.name)
+            .as_element_symbol()
+            .expect("Unreachable!");
+        let field = element.field(name)?;
+        Self::validate_struct_fields(&self.model, field.ty(), element_namespace, value)
+    }
+
+    /// Validates a list attribute element by element against the attribute's array type.
+    pub fn is_valid_list(&self, name: &str, value: &List<FieldValue>) -> Result<(), type_ml_definitions::Error> {
+        let last_element = self.depth.last().expect("Unreachable!");
+        let element_namespace = self.model.get_namespace_id(last_element.namespace.as_deref())?;
+        let element = self
+            .model
+            .get_type_by_name(element_namespace, &last_element.name)
+            .as_element_symbol()
+            .expect("Unreachable!");
+        let field = element.field(name)?;
+        Self::validate_list(
+            &self.model,
+            field.ty(),
+            element_namespace,
+            field.constraints().length(),
+            value,
+            Self::validate_field_value,
+        )
+    }
+
+    fn get_struct_impl_target(&self, value: &StructImpl) -> Result<SymbolRef, type_ml_definitions::Error> {
+        let namespace_id = self.model.get_namespace_id(value.namespace.as_deref())?;
+        match self
+            .model
+            .get_type_by_name(namespace_id, &value.identifier)
+            .unwrap_with_ref()
+        {
+            Some((target, SymbolKind::Struct(_))) => Ok(target),
+            _ => Err(type_ml_definitions::Error::StructNotFound(value.full_path())),
+        }
+    }
+
+    /// Validates the fields of a `$struct` impl against the type it implements.
+    pub fn is_valid_struct_impl(&self, value: &StructImpl) -> Result<(), type_ml_definitions::Error> {
+        let target = self.get_struct_impl_target(value)?;
+        Self::validate_struct_fields(&self.model, target, target.namespace, &value.value)
+    }
+
+    /// Checks that the `$struct` impl referenced by the attribute implements the attribute's type.
+    /// The impl fields are validated once by [`Self::is_valid_struct_impl`].
+    pub fn is_valid_struct_ref(
+        &self,
+        name: &str,
+        impl_name: &str,
+        value: &StructImpl,
+    ) -> Result<(), type_ml_definitions::Error> {
+        let last_element = self.depth.last().expect("Unreachable!");
+        let element_namespace = self.model.get_namespace_id(last_element.namespace.as_deref())?;
+        let element = self
+            .model
+            .get_type_by_name(element_namespace, &last_element.name)
+            .as_element_symbol()
+            .expect("Unreachable!");
+        let expected = Self::unwrap_option(&self.model, element.field(name)?.ty());
+        let actual = self.get_struct_impl_target(value)?;
+        if expected != actual {
+            return Err(type_ml_definitions::Error::StructImplTypeMismatch {
+                name: impl_name.to_string(),
+                expected: self.get_type_full_path(expected),
+                actual: self.get_type_full_path(actual),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Validates a struct or map literal written for `ty`. Constants in a map are looked up from `namespace`, the
+    /// ones in struct fields from the namespace of the struct.
+    fn validate_struct_fields(
+        model: &SchemaModel,
+        ty: SymbolRef,
+        namespace: usize,
+        value: &Struct,
+    ) -> Result<(), type_ml_definitions::Error> {
+        let ty = Self::unwrap_option(model, ty);
+        let kind = model.get_type_by_ref(ty);
+        let symbol = match kind.as_ref() {
+            SymbolKind::Struct(symbol) => symbol,
+            SymbolKind::Map(map) => return Self::validate_map_entries(model, map, namespace, value),
+            other => {
+                return Err(type_ml_definitions::Error::InvalidArgumentType(
+                    format!("{{{}}}", value.source.trim()),
+                    other.identifier().to_string(),
+                ));
+            }
+        };
+
+        let mut used_fields = HashSet::new();
+        for field in &value.fields {
+            let identifier = field.identifier.as_str();
+            let definition = symbol
+                .field(identifier)
+                .ok_or_else(|| type_ml_definitions::Error::FieldNotFound(identifier.to_string()))?;
+
+            if !used_fields.insert(identifier) {
+                return Err(type_ml_definitions::Error::DuplicateField(identifier.to_string()));
+            }
+
+            match &field.value {
+                FieldValue::List(list) => Self::validate_list(
+                    model,
+                    definition.ty(),
+                    ty.namespace,
+                    definition.constraints().length(),
+                    list,
+                    Self::validate_field_value,
+                )?,
+                other => {
+                    Self::validate_field_value(model, definition.ty(), ty.namespace, other)?;
+                    let value = model.expand_constant(other.as_str(), definition.ty(), ty.namespace)?;
+                    definition.constraints().check(value)?;
+                }
+            }
+        }
+
+        if let Some(field) = symbol
+            .fields
+            .iter()
+            .find(|field| field.is_required(model) && !used_fields.contains(field.identifier()))
+        {
+            return Err(type_ml_definitions::Error::MissingRequiredField(
+                field.identifier().to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks the keys of a map literal and validates every value against the map's value type.
+    fn validate_map_entries(
+        model: &SchemaModel,
+        map: &MapSymbol,
+        namespace: usize,
+        value: &Struct,
+    ) -> Result<(), type_ml_definitions::Error> {
+        map.validate_keys(value.fields.iter().map(|entry| entry.identifier.as_str()), model)?;
+        value
+            .fields
+            .iter()
+            .try_for_each(|entry| Self::validate_field_value(model, map.value(), namespace, &entry.value))
+    }
+
+    /// Returns the inner type of an `Option`, otherwise `ty`. A struct or list written for an optional value is `Some`.
+    fn unwrap_option(model: &SchemaModel, ty: SymbolRef) -> SymbolRef {
+        match model.get_type_by_ref(ty).as_ref() {
+            SymbolKind::Option(option) => option.inner(),
+            _ => ty,
+        }
+    }
+
+    fn validate_field_value(
+        model: &SchemaModel,
+        ty: SymbolRef,
+        namespace: usize,
+        value: &FieldValue,
+    ) -> Result<(), type_ml_definitions::Error> {
+        match value {
+            FieldValue::Struct(nested) => Self::validate_struct_fields(model, ty, namespace, nested),
+            FieldValue::List(list) => Self::validate_list(model, ty, namespace, None, list, Self::validate_field_value),
+            other => {
+                let value = model.expand_constant(other.as_str(), ty, namespace)?;
+                model.get_type_by_ref(ty).as_ref().can_parse(value, model)
+            }
+        }
+    }
+
+    fn validate_argument(
+        model: &SchemaModel,
+        ty: SymbolRef,
+        namespace: usize,
+        value: &ArgumentValue,
+    ) -> Result<(), type_ml_definitions::Error> {
+        match value {
+            ArgumentValue::ListValue(list) => {
+                Self::validate_list(model, ty, namespace, None, list, Self::validate_argument)
+            }
+            other => {
+                let value = model.expand_constant(other.as_str(), ty, namespace)?;
+                model.get_type_by_ref(ty).as_ref().can_parse(value, model)
+            }
+        }
+    }
+
+    /// Checks that `ty` is an array type, that the list has an allowed number of elements and that every element
+    /// is a valid value of the array's element type.
+    fn validate_list<T>(
+        model: &SchemaModel,
+        ty: SymbolRef,
+        namespace: usize,
+        length: Option<Length>,
+        list: &List<T>,
+        validate_value: fn(&SchemaModel, SymbolRef, usize, &T) -> Result<(), type_ml_definitions::Error>,
+    ) -> Result<(), type_ml_definitions::Error> {
+        let kind = model.get_type_by_ref(Self::unwrap_option(model, ty));
+        let SymbolKind::Array(array) = kind.as_ref() else {
+            return Err(type_ml_definitions::Error::InvalidArgumentType(
+                format!("[{}]", list.source.trim()),
+                kind.as_ref().identifier().to_string(),
+            ));
+        };
+
+        if let Some(length) = length {
+            length.check(list.values.len())?;
+        }
+
+        list.values
+            .iter()
+            .try_for_each(|value| validate_value(model, array.inner(), namespace, value))
     }
 
     fn is_valid_expression_element_group(
@@ -267,6 +608,7 @@ impl RmlAnalyzer {
 
     fn validate_expression_fields(
         model: &SchemaModel,
+        namespace: usize,
         expr: &ExpressionSymbol,
         expression: &Expression,
     ) -> Result<(), type_ml_definitions::Error> {
@@ -286,9 +628,18 @@ impl RmlAnalyzer {
                 }
 
                 // Validate field type
-                let field_type = model.get_type_by_ref(field.ty());
-                let field_type = field_type.as_ref();
-                field_type.can_parse(arg.value.as_str(), model)?;
+                match &arg.value {
+                    ArgumentValue::ListValue(list) => {
+                        let length = field.constraints().length();
+                        Self::validate_list(model, field.ty(), namespace, length, list, Self::validate_argument)?;
+                    }
+                    other => {
+                        Self::validate_argument(model, field.ty(), namespace, other)?;
+                        field
+                            .constraints()
+                            .check(model.expand_constant(other.as_str(), field.ty(), namespace)?)?;
+                    }
+                }
             } else {
                 // Field doesn't exist in expression definition
                 return Err(type_ml_definitions::Error::FieldNotFound(arg.identifier.to_string()));
@@ -297,7 +648,11 @@ impl RmlAnalyzer {
 
         // Check for missing required fields
         for field in expr.fields() {
-            if !field.is_optional() && !used_fields.contains(field.identifier()) {
+            if !field.is_optional()
+                && field.default().is_none()
+                && !model.get_type_by_ref(field.ty()).as_ref().is_option_symbol()
+                && !used_fields.contains(field.identifier())
+            {
                 return Err(type_ml_definitions::Error::MissingRequiredField(
                     field.identifier().to_string(),
                 ));
@@ -307,6 +662,28 @@ impl RmlAnalyzer {
         Ok(())
     }
 
+    /// A restricted expression is only valid below an element bound to one of its `@restrict` groups.
+    fn is_valid_expression_context(
+        &self,
+        expr: &ExpressionSymbol,
+        expression: &Expression,
+    ) -> Result<(), type_ml_definitions::Error> {
+        let restrict = expr.restrict();
+        if restrict.is_empty() {
+            return Ok(());
+        }
+
+        let ancestors = &self.depth[..self.depth.len().saturating_sub(1)];
+        if ancestors.iter().any(|ancestor| restrict.contains(&ancestor.group)) {
+            return Ok(());
+        }
+
+        Err(type_ml_definitions::Error::ExpressionOutsideRestrictedGroup {
+            expression: expression.full_path(),
+            groups: restrict.iter().map(|group| self.get_type_full_path(*group)).collect(),
+        })
+    }
+
     pub fn is_valid_expression(
         &self,
         element_namespace: Option<&str>,
@@ -320,7 +697,7 @@ impl RmlAnalyzer {
             .as_expression_symbol()
             .ok_or(type_ml_definitions::Error::ExpressionNotFound(expression.full_path()))?;
 
-        Self::validate_expression_fields(&self.model, expr, expression)?;
+        Self::validate_expression_fields(&self.model, expr_namespace, expr, expression)?;
         Self::is_valid_expression_element_group(
             element_namespace,
             element_name,
@@ -328,6 +705,7 @@ impl RmlAnalyzer {
             &self.model,
             expression,
         )?;
+        self.is_valid_expression_context(expr, expression)?;
 
         Ok(())
     }
@@ -18,6 +18,7 @@ pub enum RmlNode {
     StructFields,
     StructField,
     FieldValue,
+    NestedStruct,
 
     DirectiveContent,
     Directive,
@@ -34,6 +35,7 @@ pub enum RmlNode {
     ExprArg,
     ArgValue,
     ListValue,
+    ValueList,
 
     Impls,
     ImplRef,
@@ -63,6 +65,7 @@ impl CstKind for RmlNode {
             Rule::struct_fields => RmlNode::StructFields,
             Rule::struct_field => RmlNode::StructField,
             Rule::field_value => RmlNode::FieldValue,
+            Rule::nested_struct => RmlNode::NestedStruct,
             Rule::directive_content => RmlNode::DirectiveContent,
             Rule::directive => RmlNode::Directive,
             Rule::element => RmlNode::Element,
@@ -75,6 +78,7 @@ impl CstKind for RmlNode {
             Rule::expr_arg => RmlNode::ExprArg,
             Rule::arg_val => RmlNode::ArgValue,
             Rule::list_val => RmlNode::ListValue,
+            Rule::value_list => RmlNode::ValueList,
 
             Rule::impls => RmlNode::Impls,
             Rule::impl_ref => RmlNode::ImplRef,
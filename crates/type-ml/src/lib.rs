@@ -7,10 +7,13 @@ mod cst;
 mod model;
 mod resolved;
 mod unresolved;
+mod watch;
 
 use crate::analyzer::RmlAnalyzer;
 pub use crate::model::LayoutModel;
-pub use crate::unresolved::LayoutAst;
+pub use crate::resolved::*;
+pub use crate::unresolved::{Impl, LayoutAst};
+pub use crate::watch::{DependencyGraph, LayoutWatcher, ValidationReport, validate_layout};
 use lexer_core::CstNode;
 use pest_derive::Parser;
 
@@ -21,10 +24,10 @@ pub struct RmlParser;
 impl RmlParser {
     pub fn build_ast(content: &str) -> Result<LayoutAst, type_ml_definitions::Error> {
         let cst = CstNode::new::<RmlParser>(content, Rule::file).map_err(type_ml_definitions::Error::PestError)?;
-        Ok(LayoutAst::build(&cst))
+        LayoutAst::build(&cst)
     }
 }
 
 pub trait NodeResolver<T> {
-    fn resolve(&self, analyzer: &RmlAnalyzer) -> T;
+    fn resolve(&self, impls: &[Impl], analyzer: &RmlAnalyzer) -> T;
 }
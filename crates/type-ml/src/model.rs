@@ -1,34 +1,63 @@
+use crate::NodeResolver;
 use crate::analyzer::RmlAnalyzer;
-use crate::unresolved::{AttributeValue, Element, Impl, LayoutAst};
+use crate::resolved::ResolvedElement;
+use crate::unresolved::{AttributeValue, Element, Impl, ImplKind, LayoutAst, StructKind};
 use lexer_core::to_url;
+use std::collections::HashSet;
 use type_ml_definitions::{AnalysisWorkspace, SchemaModel};
 use url::Url;
 
 pub struct LayoutModel;
 impl LayoutModel {
     pub fn validate(ast: LayoutAst, path: &str) -> Result<Element, type_ml_definitions::Error> {
-        let configs = ast
-            .directives
-            .iter()
-            .filter(|d| d.name == "use")
-            .map(|d| {
-                let value = d.value.as_ref().unwrap();
-                to_url(path, value).unwrap()
-            })
-            .collect::<Vec<_>>();
+        analyze(&ast, path)?;
+        Ok(ast.root.expect("Unreachable!"))
+    }
 
-        let model = load_config_model(configs)?;
-        let mut analyzer = RmlAnalyzer::new(model);
-        let root = ast.root.unwrap();
-        validate_element(&ast.impls, &root, &mut analyzer)?;
-        Ok(root)
+    pub fn resolve(ast: LayoutAst, path: &str) -> Result<ResolvedElement, type_ml_definitions::Error> {
+        let analyzer = analyze(&ast, path)?;
+        let root = ast.root.as_ref().expect("Unreachable!");
+        Ok(root.resolve(&ast.impls, &analyzer))
     }
 }
 
+fn analyze(ast: &LayoutAst, path: &str) -> Result<RmlAnalyzer, type_ml_definitions::Error> {
+    let configs = ast
+        .directives
+        .iter()
+        .filter(|d| d.name == "use")
+        .map(|d| {
+            let value = d
+                .value
+                .as_ref()
+                .ok_or_else(|| type_ml_definitions::Error::UrlError("#use requires a path".to_string()))?;
+            to_url(path, value).map_err(type_ml_definitions::Error::UrlError)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let model = load_config_model(configs)?;
+    let mut names = HashSet::new();
+    if let Some(duplicate) = ast.impls.iter().find(|i| !names.insert(i.identifier.as_str())) {
+        return Err(type_ml_definitions::Error::DuplicateImpl(duplicate.identifier.clone()));
+    }
+
+    let mut analyzer = RmlAnalyzer::new(model);
+    ast.impls.iter().try_for_each(|i| match &i.kind {
+        ImplKind::Struct(stc) => analyzer.is_valid_struct_impl(stc),
+        ImplKind::Expr(_) => Ok(()),
+    })?;
+    let root = ast
+        .root
+        .as_ref()
+        .ok_or(type_ml_definitions::Error::RootElementNotFound)?;
+    validate_element(&ast.impls, root, &mut analyzer)?;
+    Ok(analyzer)
+}
+
 fn load_config_model(definitions: Vec<Url>) -> Result<SchemaModel, type_ml_definitions::Error> {
-    assert!(!definitions.is_empty(), "Definition file not found");
     let mut iter = definitions.into_iter();
-    AnalysisWorkspace::new(iter.next().unwrap()).run()
+    let main = iter.next().ok_or(type_ml_definitions::Error::DefinitionsNotFound)?;
+    AnalysisWorkspace::new(main).run()
 }
 
 fn validate_element(
@@ -38,29 +67,32 @@ fn validate_element(
 ) -> Result<(), type_ml_definitions::Error> {
     let namespace = element.namespace.as_deref();
     let identifier = &element.identifier;
-    if analyzer.is_allowed_element(namespace, identifier)? {
-        analyzer.enter_element(namespace, identifier)?;
-        element.attributes.iter().try_for_each(|attr| {
-            match &attr.value {
-                AttributeValue::Expression(expr) => {
-                    let expr = expr.as_expr(impls);
-                    analyzer.is_valid_expression(element.namespace.as_deref(), &element.identifier, expr)
-                }
-                AttributeValue::Struct(kind) => {
-                    let stc = kind.as_struct(impls);
-                    analyzer.is_valid_attribute(&attr.identifier, stc.source.as_str())
-                }
-                other => analyzer.is_valid_attribute(&attr.identifier, other.as_str()),
-            }?;
-            Ok::<(), type_ml_definitions::Error>(())
-        })?;
-        element
-            .children
-            .iter()
-            .try_for_each(|child| validate_element(impls, child, analyzer))?;
-        analyzer.exit_element(namespace, identifier)?;
-        Ok(())
-    } else {
-        panic!("Incorrect element: {}", element.identifier);
+    if !analyzer.is_allowed_element(namespace, identifier)? {
+        return Err(analyzer.element_not_allowed(namespace, identifier));
     }
+
+    analyzer.enter_element(namespace, identifier)?;
+    element.attributes.iter().try_for_each(|attr| {
+        analyzer.add_attribute(&attr.identifier)?;
+        match &attr.value {
+            AttributeValue::Expression(expr) => {
+                let expr = expr.as_expr(impls)?;
+                analyzer.is_valid_expression(element.namespace.as_deref(), &element.identifier, expr)
+            }
+            AttributeValue::Struct(StructKind::Ref(impl_name)) => {
+                let stc = Impl::find_struct(impls, impl_name)?;
+                analyzer.is_valid_struct_ref(&attr.identifier, impl_name, stc)
+            }
+            AttributeValue::Struct(StructKind::Impl(stc)) => analyzer.is_valid_struct(&attr.identifier, stc),
+            AttributeValue::List(list) => analyzer.is_valid_list(&attr.identifier, list),
+            other => analyzer.is_valid_attribute(&attr.identifier, other.as_str()),
+        }?;
+        Ok::<(), type_ml_definitions::Error>(())
+    })?;
+    element
+        .children
+        .iter()
+        .try_for_each(|child| validate_element(impls, child, analyzer))?;
+    analyzer.exit_element(namespace, identifier)?;
+    Ok(())
 }
@@ -1,5 +1,12 @@
+use crate::NodeResolver;
+use crate::analyzer::RmlAnalyzer;
+use crate::unresolved::{ArgumentValue, AttributeValue, Element, Expression, FieldValue, Impl, List, Struct};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use type_ml_definitions::{BaseType, Char, Color, MapSymbol, OptionSymbol, SchemaModel, Symbol, SymbolKind, SymbolRef};
+use url::Url;
 
+#[derive(Debug)]
 pub enum ResolvedBaseType {
     F32(f32),
     F64(f64),
@@ -11,10 +18,19 @@ pub enum ResolvedBaseType {
     U16(u16),
     U32(u32),
     U64(u64),
+    I128(i128),
+    U128(u128),
+    Isize(isize),
+    Usize(usize),
     Boolean(bool),
+    Char(char),
     String(String),
+    Color(Color),
+    Path(PathBuf),
+    Url(Url),
 }
 
+#[derive(Debug)]
 pub struct ResolvedExpressionField {
     identifier: String,
     value: ResolvedType,
@@ -30,13 +46,19 @@ impl ResolvedExpressionField {
     }
 }
 
+#[derive(Debug)]
 pub struct ResolvedExpression {
+    namespace: Option<String>,
     identifier: String,
     arguments: Vec<ResolvedExpressionField>,
     metadata: HashMap<String, Option<ResolvedBaseType>>,
 }
 
 impl ResolvedExpression {
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
     pub const fn identifier(&self) -> &str {
         self.identifier.as_str()
     }
@@ -50,9 +72,10 @@ impl ResolvedExpression {
     }
 }
 
+#[derive(Debug)]
 pub struct ResolvedField {
     identifier: String,
-    value: ResolvedBaseType,
+    value: ResolvedType,
 }
 
 impl ResolvedField {
@@ -60,11 +83,28 @@ impl ResolvedField {
         self.identifier.as_str()
     }
 
-    pub const fn value(&self) -> &ResolvedBaseType {
+    pub const fn value(&self) -> &ResolvedType {
+        &self.value
+    }
+}
+
+#[derive(Debug)]
+pub struct ResolvedEntry {
+    key: ResolvedType,
+    value: ResolvedType,
+}
+
+impl ResolvedEntry {
+    pub const fn key(&self) -> &ResolvedType {
+        &self.key
+    }
+
+    pub const fn value(&self) -> &ResolvedType {
         &self.value
     }
 }
 
+#[derive(Debug)]
 pub struct ResolvedStruct {
     fields: Vec<ResolvedField>,
     metadata: HashMap<String, Option<ResolvedBaseType>>,
@@ -80,14 +120,36 @@ impl ResolvedStruct {
     }
 }
 
+#[derive(Debug)]
+pub struct ResolvedVariant {
+    identifier: String,
+    payload: Option<Box<ResolvedType>>,
+}
+
+impl ResolvedVariant {
+    pub const fn identifier(&self) -> &str {
+        self.identifier.as_str()
+    }
+
+    pub fn payload(&self) -> Option<&ResolvedType> {
+        self.payload.as_deref()
+    }
+}
+
+#[derive(Debug)]
 pub enum ResolvedType {
     Base(ResolvedBaseType),
-    Enum(String),
+    Enum(ResolvedVariant),
     Struct(ResolvedStruct),
     Expression(ResolvedExpression),
     List(Vec<ResolvedType>),
+    /// A value of an `Option<T>` field. Omitted fields without a default are `None`.
+    Option(Option<Box<ResolvedType>>),
+    /// The entries of a `Map<K, V>` in the order they are written.
+    Map(Vec<ResolvedEntry>),
 }
 
+#[derive(Debug)]
 pub struct ResolvedAttribute {
     identifier: String,
     value: ResolvedType,
@@ -103,7 +165,9 @@ impl ResolvedAttribute {
     }
 }
 
+#[derive(Debug)]
 pub struct ResolvedElement {
+    namespace: Option<String>,
     identifier: String,
     attributes: Vec<ResolvedAttribute>,
     children: Vec<ResolvedElement>,
@@ -111,6 +175,10 @@ pub struct ResolvedElement {
 }
 
 impl ResolvedElement {
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
     pub const fn identifier(&self) -> &str {
         self.identifier.as_str()
     }
@@ -127,3 +195,295 @@ impl ResolvedElement {
         &self.metadata
     }
 }
+
+fn trim_quotes(s: &str) -> &str {
+    let s = s.strip_prefix('"').unwrap_or(s);
+    s.strip_suffix('"').unwrap_or(s)
+}
+
+fn resolve_metadata(metadata: &HashMap<String, Option<BaseType>>) -> HashMap<String, Option<ResolvedBaseType>> {
+    metadata
+        .iter()
+        .map(|(name, value)| {
+            let value = value.as_ref().map(|value| match value {
+                BaseType::Number(number) => number
+                    .parse()
+                    .map(ResolvedBaseType::I64)
+                    .unwrap_or_else(|_| ResolvedBaseType::F64(number.parse().unwrap_or_default())),
+                BaseType::Boolean(boolean) => ResolvedBaseType::Boolean(*boolean),
+                BaseType::String(string) => ResolvedBaseType::String(trim_quotes(string).to_string()),
+                BaseType::Ident(ident) => ResolvedBaseType::String(ident.clone()),
+            });
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+fn resolve_value(value: &str, ty: SymbolRef, namespace: usize, model: &SchemaModel) -> ResolvedType {
+    let value = model.expand_constant(value, ty, namespace).expect("Unreachable!");
+    let base = match model.get_type_by_ref(ty).as_ref() {
+        SymbolKind::Bool(_) => ResolvedBaseType::Boolean(value.parse().expect("Unreachable!")),
+        SymbolKind::F32(_) => ResolvedBaseType::F32(value.parse().expect("Unreachable!")),
+        SymbolKind::F64(_) => ResolvedBaseType::F64(value.parse().expect("Unreachable!")),
+        SymbolKind::I8(_) => ResolvedBaseType::I8(value.parse().expect("Unreachable!")),
+        SymbolKind::I16(_) => ResolvedBaseType::I16(value.parse().expect("Unreachable!")),
+        SymbolKind::I32(_) => ResolvedBaseType::I32(value.parse().expect("Unreachable!")),
+        SymbolKind::I64(_) => ResolvedBaseType::I64(value.parse().expect("Unreachable!")),
+        SymbolKind::U8(_) => ResolvedBaseType::U8(value.parse().expect("Unreachable!")),
+        SymbolKind::U16(_) => ResolvedBaseType::U16(value.parse().expect("Unreachable!")),
+        SymbolKind::U32(_) => ResolvedBaseType::U32(value.parse().expect("Unreachable!")),
+        SymbolKind::U64(_) => ResolvedBaseType::U64(value.parse().expect("Unreachable!")),
+        SymbolKind::I128(_) => ResolvedBaseType::I128(value.parse().expect("Unreachable!")),
+        SymbolKind::U128(_) => ResolvedBaseType::U128(value.parse().expect("Unreachable!")),
+        SymbolKind::Isize(_) => ResolvedBaseType::Isize(value.parse().expect("Unreachable!")),
+        SymbolKind::Usize(_) => ResolvedBaseType::Usize(value.parse().expect("Unreachable!")),
+        SymbolKind::Char(_) => ResolvedBaseType::Char(Char::parse(value).expect("Unreachable!")),
+        SymbolKind::String(_) => ResolvedBaseType::String(trim_quotes(value).to_string()),
+        SymbolKind::Color(_) => ResolvedBaseType::Color(Color::parse(value).expect("Unreachable!")),
+        SymbolKind::Path(_) => ResolvedBaseType::Path(PathBuf::from(trim_quotes(value))),
+        SymbolKind::Url(_) => ResolvedBaseType::Url(Url::parse(trim_quotes(value)).expect("Unreachable!")),
+        SymbolKind::Option(option) => {
+            let value = (!OptionSymbol::is_none(value))
+                .then(|| Box::new(resolve_value(value, option.inner(), namespace, model)));
+            return ResolvedType::Option(value);
+        }
+        SymbolKind::Enum(symbol) => {
+            let (variant, payload) = symbol.match_variant(value).expect("Unreachable!");
+            let payload = variant
+                .ty
+                .as_ref()
+                .zip(payload)
+                .map(|(ty, payload)| Box::new(resolve_value(payload, ty.as_concrete(), namespace, model)));
+            return ResolvedType::Enum(ResolvedVariant {
+                identifier: variant.identifier.clone(),
+                payload,
+            });
+        }
+        _ => ResolvedBaseType::String(value.to_string()),
+    };
+
+    ResolvedType::Base(base)
+}
+
+/// Resolves a struct or list written for `ty`, wrapping it in `Some` if `ty` is an `Option`.
+fn resolve_optional(
+    ty: SymbolRef,
+    model: &SchemaModel,
+    resolve: impl FnOnce(SymbolRef) -> ResolvedType,
+) -> ResolvedType {
+    match model.get_type_by_ref(ty).as_ref() {
+        SymbolKind::Option(option) => ResolvedType::Option(Some(Box::new(resolve(option.inner())))),
+        _ => resolve(ty),
+    }
+}
+
+/// Appends the fields that aren't written, given as `(identifier, default, type)`: their default, or `None`
+/// for an `Option` without one. `namespace` is the namespace of the symbol declaring the fields.
+fn push_omitted<'a, T>(
+    written: &mut Vec<T>,
+    fields: impl IntoIterator<Item = (&'a str, Option<&'a str>, SymbolRef)>,
+    identifier: impl Fn(&T) -> &str,
+    namespace: usize,
+    model: &SchemaModel,
+    new: impl Fn(String, ResolvedType) -> T,
+) {
+    for (field, default, ty) in fields {
+        if written.iter().any(|value| identifier(value) == field) {
+            continue;
+        }
+
+        let value = match default {
+            Some(default) => resolve_value(default, ty, namespace, model),
+            None if model.get_type_by_ref(ty).as_ref().is_option_symbol() => ResolvedType::Option(None),
+            None => continue,
+        };
+        written.push(new(field.to_string(), value));
+    }
+}
+
+fn resolve_argument(value: &ArgumentValue, ty: SymbolRef, namespace: usize, model: &SchemaModel) -> ResolvedType {
+    match value {
+        ArgumentValue::ListValue(list) => resolve_optional(ty, model, |ty| {
+            let Some(SymbolKind::Array(array)) = model.get_type_by_ref(ty).unwrap() else {
+                unreachable!()
+            };
+            ResolvedType::List(
+                list.values
+                    .iter()
+                    .map(|value| resolve_argument(value, array.inner(), namespace, model))
+                    .collect(),
+            )
+        }),
+        other => resolve_value(other.as_str(), ty, namespace, model),
+    }
+}
+
+fn resolve_field_value(value: &FieldValue, ty: SymbolRef, namespace: usize, model: &SchemaModel) -> ResolvedType {
+    match value {
+        FieldValue::Struct(nested) => resolve_optional(ty, model, |ty| resolve_struct(nested, ty, namespace, model)),
+        FieldValue::List(list) => resolve_optional(ty, model, |ty| resolve_list(list, ty, namespace, model)),
+        other => resolve_value(other.as_str(), ty, namespace, model),
+    }
+}
+
+fn resolve_list(list: &List<FieldValue>, ty: SymbolRef, namespace: usize, model: &SchemaModel) -> ResolvedType {
+    let Some(SymbolKind::Array(array)) = model.get_type_by_ref(ty).unwrap() else {
+        unreachable!()
+    };
+
+    ResolvedType::List(
+        list.values
+            .iter()
+            .map(|value| resolve_field_value(value, array.inner(), namespace, model))
+            .collect(),
+    )
+}
+
+/// Resolves a struct or map literal. Constants in a map are looked up from `namespace`, the ones in struct fields
+/// from the namespace of the struct.
+fn resolve_struct(value: &Struct, ty: SymbolRef, namespace: usize, model: &SchemaModel) -> ResolvedType {
+    let symbol = match model.get_type_by_ref(ty).unwrap() {
+        Some(SymbolKind::Struct(symbol)) => symbol,
+        Some(SymbolKind::Map(map)) => return resolve_map(value, map, namespace, model),
+        _ => unreachable!(),
+    };
+
+    let mut fields = value
+        .fields
+        .iter()
+        .map(|field| {
+            let field_ty = symbol.field(&field.identifier).expect("Unreachable!").ty();
+            ResolvedField {
+                identifier: field.identifier.clone(),
+                value: resolve_field_value(&field.value, field_ty, ty.namespace, model),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let omitted = symbol.fields.iter().map(|f| (f.identifier(), f.default(), f.ty()));
+    push_omitted(
+        &mut fields,
+        omitted,
+        |f| &f.identifier,
+        ty.namespace,
+        model,
+        |identifier, value| ResolvedField { identifier, value },
+    );
+
+    ResolvedType::Struct(ResolvedStruct {
+        fields,
+        metadata: resolve_metadata(&symbol.metadata),
+    })
+}
+
+fn resolve_map(value: &Struct, map: &MapSymbol, namespace: usize, model: &SchemaModel) -> ResolvedType {
+    ResolvedType::Map(
+        value
+            .fields
+            .iter()
+            .map(|entry| ResolvedEntry {
+                key: resolve_value(&entry.identifier, map.key(), namespace, model),
+                value: resolve_field_value(&entry.value, map.value(), namespace, model),
+            })
+            .collect(),
+    )
+}
+
+impl NodeResolver<ResolvedExpression> for Expression {
+    fn resolve(&self, _: &[Impl], analyzer: &RmlAnalyzer) -> ResolvedExpression {
+        let model = analyzer.model();
+        let namespace = model.get_namespace_id(self.namespace.as_deref()).expect("Unreachable!");
+        let symbol = model
+            .get_type_by_name(namespace, &self.identifier)
+            .as_expression_symbol()
+            .expect("Unreachable!");
+
+        let mut arguments = self
+            .arguments
+            .iter()
+            .map(|argument| {
+                let ty = symbol.field(&argument.identifier).expect("Unreachable!").ty();
+                ResolvedExpressionField {
+                    identifier: argument.identifier.clone(),
+                    value: resolve_argument(&argument.value, ty, namespace, model),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let omitted = symbol.fields().iter().map(|f| (f.identifier(), f.default(), f.ty()));
+        push_omitted(
+            &mut arguments,
+            omitted,
+            |a| &a.identifier,
+            namespace,
+            model,
+            |identifier, value| ResolvedExpressionField { identifier, value },
+        );
+
+        ResolvedExpression {
+            namespace: self.namespace.clone(),
+            identifier: symbol.identifier().to_string(),
+            arguments,
+            metadata: resolve_metadata(symbol.metadata()),
+        }
+    }
+}
+
+impl NodeResolver<ResolvedElement> for Element {
+    fn resolve(&self, impls: &[Impl], analyzer: &RmlAnalyzer) -> ResolvedElement {
+        let model = analyzer.model();
+        let namespace = model.get_namespace_id(self.namespace.as_deref()).expect("Unreachable!");
+        let symbol = model
+            .get_type_by_name(namespace, &self.identifier)
+            .as_element_symbol()
+            .expect("Unreachable!");
+
+        let mut attributes = self
+            .attributes
+            .iter()
+            .map(|attribute| {
+                let ty = symbol.field(&attribute.identifier).expect("Unreachable!").ty();
+                let value = match &attribute.value {
+                    AttributeValue::Expression(expr) => {
+                        ResolvedType::Expression(expr.as_expr(impls).expect("Unreachable!").resolve(impls, analyzer))
+                    }
+                    AttributeValue::Struct(kind) => resolve_optional(ty, model, |ty| {
+                        resolve_struct(kind.as_struct(impls).expect("Unreachable!"), ty, namespace, model)
+                    }),
+                    AttributeValue::List(list) => {
+                        resolve_optional(ty, model, |ty| resolve_list(list, ty, namespace, model))
+                    }
+                    other => resolve_value(other.as_str(), ty, namespace, model),
+                };
+                ResolvedAttribute {
+                    identifier: attribute.identifier.clone(),
+                    value,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let omitted = symbol.fields().iter().map(|f| (f.identifier(), f.default(), f.ty()));
+        push_omitted(
+            &mut attributes,
+            omitted,
+            |a| &a.identifier,
+            namespace,
+            model,
+            |identifier, value| ResolvedAttribute { identifier, value },
+        );
+
+        let children = self
+            .children
+            .iter()
+            .map(|child| child.resolve(impls, analyzer))
+            .collect();
+
+        ResolvedElement {
+            namespace: self.namespace.clone(),
+            identifier: self.identifier.clone(),
+            attributes,
+            children,
+            metadata: resolve_metadata(symbol.metadata()),
+        }
+    }
+}
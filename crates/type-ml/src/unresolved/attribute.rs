@@ -1,6 +1,6 @@
 use crate::cst::RmlNode;
-use crate::unresolved::expression::ExpressionKind;
-use crate::unresolved::structure::StructKind;
+use crate::unresolved::expression::{ExpressionKind, List};
+use crate::unresolved::structure::{FieldValue, StructKind};
 use lexer_core::CstNode;
 
 #[derive(Debug)]
@@ -11,6 +11,7 @@ pub enum AttributeValue {
     Enum(String),
     Struct(StructKind),
     Expression(ExpressionKind),
+    List(List<FieldValue>),
 }
 
 impl AttributeValue {
@@ -23,6 +24,7 @@ impl AttributeValue {
             RmlNode::String => AttributeValue::String(node.text.to_string()),
             RmlNode::Expression => AttributeValue::Expression(ExpressionKind::build(child)),
             RmlNode::Struct => AttributeValue::Struct(StructKind::build(child)),
+            RmlNode::ValueList => AttributeValue::List(List::build(child, FieldValue::build)),
             _ => unreachable!(),
         }
     }
@@ -40,6 +42,7 @@ impl AttributeValue {
             AttributeValue::String(value) => value.as_str(),
             AttributeValue::Enum(value) => value.as_str(),
             AttributeValue::Struct(value) => value.as_str(),
+            AttributeValue::List(value) => value.source.as_str(),
             AttributeValue::Expression(_) => unreachable!(),
         }
     }
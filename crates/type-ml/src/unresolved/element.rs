@@ -1,7 +1,10 @@
 use crate::cst::RmlNode;
-use crate::unresolved::attribute::Attribute;
+use crate::unresolved::attribute::{Attribute, AttributeValue};
 use crate::unresolved::build_ident;
+use crate::unresolved::expression::ExpressionKind;
+use crate::unresolved::structure::StructKind;
 use lexer_core::CstNode;
+use std::collections::HashSet;
 
 #[derive(Debug)]
 pub struct Element {
@@ -12,31 +15,49 @@ pub struct Element {
 }
 
 impl Element {
-    fn build_element_from_tag(node: &CstNode<RmlNode>) -> Element {
+    fn build_element_from_tag(node: &CstNode<RmlNode>) -> Result<Element, type_ml_definitions::Error> {
         let (open_ns, open_ident) = build_ident(node.children.first().unwrap());
         let (close_ns, close_ident) = build_ident(node.children.last().unwrap());
-        let mut alias = String::new();
+        if open_ns != close_ns || open_ident != close_ident {
+            return Err(type_ml_definitions::Error::MismatchedClosingTag {
+                expected: super::full_path(open_ns.as_deref(), &open_ident),
+                found: super::full_path(close_ns.as_deref(), &close_ident),
+            });
+        }
+
+        let mut _alias = String::new();
         let mut children = vec![];
         let mut attributes = vec![];
 
-        node.children[1..node.children.len() - 1]
-            .iter()
-            .for_each(|c| match c.kind {
-                RmlNode::Alias => alias = build_alias(c),
-                RmlNode::Element => children.push(Element::build(c)),
+        for c in &node.children[1..node.children.len() - 1] {
+            match c.kind {
+                RmlNode::Alias => _alias = build_alias(c),
+                RmlNode::Element => children.push(Element::build(c)?),
                 RmlNode::Attribute => attributes.push(Attribute::build(c)),
                 kind => unreachable!("{kind:#?}"),
-            });
-
-        //assert_eq!(open_ns, close_ns);
-        //assert_eq!(open_ident, close_ident);
+            }
+        }
 
-        Element {
+        Ok(Element {
             namespace: open_ns,
             identifier: open_ident,
             attributes,
             children,
+        })
+    }
+
+    /// Collects the names of the impls referenced by this element and its children.
+    pub(crate) fn collect_impl_refs<'a>(&'a self, refs: &mut HashSet<&'a str>) {
+        for attribute in &self.attributes {
+            match &attribute.value {
+                AttributeValue::Expression(ExpressionKind::Ref(r)) | AttributeValue::Struct(StructKind::Ref(r)) => {
+                    refs.insert(r.as_str());
+                }
+                _ => {}
+            }
         }
+
+        self.children.iter().for_each(|child| child.collect_impl_refs(refs));
     }
 
     fn build_element_from_empty_tag(node: &CstNode<RmlNode>) -> Element {
@@ -58,11 +79,11 @@ impl Element {
         }
     }
 
-    pub fn build(node: &CstNode<RmlNode>) -> Self {
+    pub fn build(node: &CstNode<RmlNode>) -> Result<Self, type_ml_definitions::Error> {
         let child = node.children.first().unwrap();
         match child.kind {
             RmlNode::Tag => Self::build_element_from_tag(child),
-            RmlNode::EmptyTag => Self::build_element_from_empty_tag(child),
+            RmlNode::EmptyTag => Ok(Self::build_element_from_empty_tag(child)),
             _ => unreachable!(),
         }
     }
@@ -1,16 +1,17 @@
 use crate::cst::RmlNode;
-use crate::unresolved::build_ident;
-use crate::unresolved::implements::{Impl, ImplKind};
+use crate::unresolved::implements::Impl;
+use crate::unresolved::{build_ident, full_path};
 use lexer_core::CstNode;
 
+/// A `[a, b, c]` literal. The source is kept without the brackets.
 #[derive(Debug)]
-pub struct List {
+pub struct List<T> {
     pub source: String,
-    pub values: Vec<ArgumentValue>,
+    pub values: Vec<T>,
 }
 
-impl List {
-    fn build(node: &CstNode<RmlNode>) -> Self {
+impl<T> List<T> {
+    pub(crate) fn build(node: &CstNode<RmlNode>, build_value: impl Fn(&CstNode<RmlNode>) -> T) -> Self {
         let source = node
             .text
             .strip_prefix("[")
@@ -19,7 +20,7 @@ impl List {
             .unwrap()
             .to_string();
 
-        let values = node.children.iter().map(ArgumentValue::build).collect();
+        let values = node.children.iter().map(build_value).collect();
         List { source, values }
     }
 }
@@ -30,7 +31,7 @@ pub enum ArgumentValue {
     Number(String),
     Boolean(bool),
     Enum(String),
-    ListValue(List),
+    ListValue(List<ArgumentValue>),
 }
 
 impl ArgumentValue {
@@ -57,7 +58,7 @@ impl ArgumentValue {
             RmlNode::Number => ArgumentValue::Number(node.text.to_string()),
             RmlNode::Boolean => ArgumentValue::Boolean(str::parse(&node.text).unwrap()),
             RmlNode::String => ArgumentValue::String(node.text.to_string()),
-            RmlNode::ListValue => ArgumentValue::ListValue(List::build(child)),
+            RmlNode::ListValue => ArgumentValue::ListValue(List::build(child, ArgumentValue::build)),
             _ => unreachable!(),
         }
     }
@@ -88,8 +89,7 @@ pub struct Expression {
 impl Expression {
     #[must_use]
     pub fn full_path(&self) -> String {
-        let ns = self.namespace.clone().unwrap_or_default();
-        format!("{ns}::{}", self.identifier)
+        full_path(self.namespace.as_deref(), &self.identifier)
     }
 
     pub fn build_expression_arguments(node: &CstNode<RmlNode>) -> Vec<ExpressionArgument> {
@@ -108,17 +108,10 @@ pub enum ExpressionKind {
 }
 
 impl ExpressionKind {
-    pub fn as_expr<'a>(&'a self, impls: &'a [Impl]) -> &'a Expression {
+    pub fn as_expr<'a>(&'a self, impls: &'a [Impl]) -> Result<&'a Expression, type_ml_definitions::Error> {
         match self {
-            ExpressionKind::Ref(r) => impls
-                .iter()
-                .find(|i| i.identifier.as_str() == r)
-                .map(|i| match &i.kind {
-                    ImplKind::Expr(e) => e,
-                    ImplKind::Struct(_) => unreachable!(),
-                })
-                .unwrap(),
-            ExpressionKind::Impl(i) => i,
+            ExpressionKind::Ref(r) => Impl::find_expr(impls, r),
+            ExpressionKind::Impl(i) => Ok(i),
         }
     }
 
@@ -1,13 +1,27 @@
 use crate::cst::RmlNode;
-use crate::unresolved::build_ident;
 use crate::unresolved::expression::Expression;
 use crate::unresolved::structure::Struct;
+use crate::unresolved::{build_ident, full_path};
 use lexer_core::CstNode;
 
 #[derive(Debug)]
 pub enum ImplKind {
     Expr(Expression),
-    Struct(Struct),
+    Struct(StructImpl),
+}
+
+/// A `$struct` impl together with the struct type it implements.
+#[derive(Debug)]
+pub struct StructImpl {
+    pub namespace: Option<String>,
+    pub identifier: String,
+    pub value: Struct,
+}
+
+impl StructImpl {
+    pub fn full_path(&self) -> String {
+        full_path(self.namespace.as_deref(), &self.identifier)
+    }
 }
 
 #[derive(Debug)]
@@ -17,6 +31,41 @@ pub struct Impl {
 }
 
 impl Impl {
+    pub fn find<'a>(impls: &'a [Impl], name: &str) -> Result<&'a Impl, type_ml_definitions::Error> {
+        impls
+            .iter()
+            .find(|i| i.identifier == name)
+            .ok_or_else(|| type_ml_definitions::Error::ImplNotFound(name.to_string()))
+    }
+
+    pub fn find_expr<'a>(impls: &'a [Impl], name: &str) -> Result<&'a Expression, type_ml_definitions::Error> {
+        let found = Self::find(impls, name)?;
+        match &found.kind {
+            ImplKind::Expr(e) => Ok(e),
+            ImplKind::Struct(_) => Err(found.kind_mismatch("$expr")),
+        }
+    }
+
+    pub fn find_struct<'a>(impls: &'a [Impl], name: &str) -> Result<&'a StructImpl, type_ml_definitions::Error> {
+        let found = Self::find(impls, name)?;
+        match &found.kind {
+            ImplKind::Struct(s) => Ok(s),
+            ImplKind::Expr(_) => Err(found.kind_mismatch("$struct")),
+        }
+    }
+
+    fn kind_mismatch(&self, expected: &str) -> type_ml_definitions::Error {
+        let actual = match self.kind {
+            ImplKind::Expr(_) => "$expr",
+            ImplKind::Struct(_) => "$struct",
+        };
+        type_ml_definitions::Error::ImplKindMismatch {
+            name: self.identifier.clone(),
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        }
+    }
+
     pub fn build(node: &CstNode<RmlNode>) -> Impl {
         let child = node.children.first().unwrap();
         match child.kind {
@@ -52,9 +101,13 @@ fn build_struct_impl(node: &CstNode<RmlNode>) -> Impl {
     let fields_node = iter.next().unwrap();
     let fields = Struct::build_struct_fields(fields_node);
 
-    let r#struct = Struct {
-        source: fields_node.text.clone(),
-        fields,
+    let r#struct = StructImpl {
+        namespace: definition_ns,
+        identifier: definition_ident,
+        value: Struct {
+            source: fields_node.text.clone(),
+            fields,
+        },
     };
 
     Impl {
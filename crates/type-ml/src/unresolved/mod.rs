@@ -1,5 +1,6 @@
 use crate::cst::RmlNode;
 use lexer_core::CstNode;
+use std::collections::HashSet;
 
 mod attribute;
 mod directive;
@@ -23,8 +24,7 @@ pub struct LayoutAst {
 }
 
 impl LayoutAst {
-    #[must_use]
-    pub fn build(cst: &CstNode<RmlNode>) -> LayoutAst {
+    pub fn build(cst: &CstNode<RmlNode>) -> Result<LayoutAst, type_ml_definitions::Error> {
         let mut impls = Vec::new();
         let mut directives = Vec::new();
         let mut root = None;
@@ -32,21 +32,41 @@ impl LayoutAst {
         for child in &cst.children {
             match child.kind {
                 RmlNode::Directive => directives.push(Directive::build(child)),
-                RmlNode::Element => root = Some(Element::build(child)),
+                RmlNode::Element => root = Some(Element::build(child)?),
                 RmlNode::Impls => impls.push(Impl::build(child)),
                 RmlNode::Symbol => {}
                 _ => unreachable!("{:#?}", child.kind),
             }
         }
 
-        LayoutAst {
+        Ok(LayoutAst {
             directives,
             root,
             impls,
+        })
+    }
+
+    /// Returns the impls that no attribute refers to.
+    #[must_use]
+    pub fn unused_impls(&self) -> Vec<&Impl> {
+        let mut used = HashSet::new();
+        if let Some(root) = &self.root {
+            root.collect_impl_refs(&mut used);
         }
+
+        self.impls
+            .iter()
+            .filter(|i| !used.contains(i.identifier.as_str()))
+            .collect()
     }
 }
 
+/// Formats a layout identifier like [`type_ml_definitions::SchemaModel::type_path`]: `ns::Name`, or `Name` in the
+/// global namespace.
+pub(crate) fn full_path(namespace: Option<&str>, identifier: &str) -> String {
+    namespace.map_or_else(|| identifier.to_string(), |ns| format!("{ns}::{identifier}"))
+}
+
 fn build_ident(node: &CstNode<RmlNode>) -> (Option<String>, String) {
     match node.kind {
         RmlNode::Ident => (None, node.text.to_string()),
@@ -1,5 +1,6 @@
 use crate::cst::RmlNode;
-use crate::unresolved::implements::{Impl, ImplKind};
+use crate::unresolved::expression::List;
+use crate::unresolved::implements::Impl;
 use lexer_core::CstNode;
 
 #[derive(Debug)]
@@ -8,16 +9,42 @@ pub enum FieldValue {
     Number(String),
     Enum(String),
     Boolean(bool),
+    Struct(Struct),
+    List(List<FieldValue>),
 }
 
 impl FieldValue {
-    fn build(node: &CstNode<RmlNode>) -> FieldValue {
+    pub fn as_str(&self) -> &str {
+        match self {
+            FieldValue::String(value) | FieldValue::Number(value) | FieldValue::Enum(value) => value.as_str(),
+            FieldValue::Struct(value) => value.source.as_str(),
+            FieldValue::List(value) => value.source.as_str(),
+            FieldValue::Boolean(value) => {
+                if *value {
+                    "true"
+                } else {
+                    "false"
+                }
+            }
+        }
+    }
+
+    pub(crate) fn build(node: &CstNode<RmlNode>) -> FieldValue {
         let child = node.children.first().unwrap();
         match child.kind {
             RmlNode::String => FieldValue::String(node.text.to_string()),
             RmlNode::Number => FieldValue::Number(node.text.to_string()),
             RmlNode::Boolean => FieldValue::Boolean(str::parse(&node.text).unwrap()),
             RmlNode::EnumValue => FieldValue::Enum(node.text.to_string()),
+            RmlNode::NestedStruct => FieldValue::Struct(match child.children.first() {
+                Some(fields_node) => Struct {
+                    source: fields_node.text.clone(),
+                    fields: Struct::build_struct_fields(fields_node),
+                },
+                // An empty `{}`
+                None => Struct::default(),
+            }),
+            RmlNode::ValueList => FieldValue::List(List::build(child, FieldValue::build)),
             _ => unreachable!(),
         }
     }
@@ -25,6 +52,7 @@ impl FieldValue {
 
 #[derive(Debug)]
 pub struct StructField {
+    /// The field name, or the key of a map entry, which keeps the quotes of a string key.
     pub identifier: String,
     pub value: FieldValue,
 }
@@ -38,7 +66,7 @@ impl StructField {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Struct {
     pub source: String,
     pub fields: Vec<StructField>,
@@ -68,17 +96,10 @@ impl StructKind {
         }
     }
 
-    pub fn as_struct<'a>(&'a self, impls: &'a [Impl]) -> &'a Struct {
+    pub fn as_struct<'a>(&'a self, impls: &'a [Impl]) -> Result<&'a Struct, type_ml_definitions::Error> {
         match self {
-            StructKind::Ref(r) => impls
-                .iter()
-                .find(|i| i.identifier.as_str() == r)
-                .map(|i| match &i.kind {
-                    ImplKind::Struct(s) => s,
-                    ImplKind::Expr(_) => unreachable!(),
-                })
-                .unwrap(),
-            StructKind::Impl(i) => i,
+            StructKind::Ref(r) => Impl::find_struct(impls, r).map(|s| &s.value),
+            StructKind::Impl(i) => Ok(i),
         }
     }
 
@@ -91,14 +112,16 @@ impl StructKind {
             .unwrap()
             .to_string();
 
-        let child = node.children.first().unwrap();
-        match child.kind {
-            RmlNode::ImplRef => StructKind::Ref(child.children.first().unwrap().text.clone()),
-            RmlNode::StructFields => StructKind::Impl(Struct {
+        match node.children.first() {
+            Some(child) if child.kind == RmlNode::ImplRef => {
+                StructKind::Ref(child.children.first().unwrap().text.clone())
+            }
+            Some(child) if child.kind == RmlNode::StructFields => StructKind::Impl(Struct {
                 source,
                 fields: Struct::build_struct_fields(child),
             }),
-            _ => unreachable!(),
+            Some(_) => unreachable!(),
+            None => StructKind::Impl(Struct { source, fields: vec![] }),
         }
     }
 }
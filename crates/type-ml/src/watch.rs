@@ -0,0 +1,283 @@
+use crate::{LayoutModel, RmlParser};
+use lexer_core::to_url;
+use notify::event::EventKind;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, channel};
+use std::time::Duration;
+use type_ml_definitions::{Error, LoadError, RmlxParser};
+
+/// Editors usually emit several events per save, so they are collected for a short while before revalidating.
+const DEBOUNCE: Duration = Duration::from_millis(50);
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some(extension)
+}
+
+fn is_layout(path: &Path) -> bool {
+    has_extension(path, "tml")
+}
+
+fn is_source(path: &Path) -> bool {
+    is_layout(path) || has_extension(path, "tmd")
+}
+
+/// Reads the `#use` directives of a layout or schema.
+/// Returns `None` if the file can't be read or parsed.
+fn read_dependencies(path: &Path) -> Option<Vec<PathBuf>> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let uses = if is_layout(path) {
+        let ast = RmlParser::build_ast(&content).ok()?;
+        ast.directives
+            .into_iter()
+            .filter(|d| d.name == "use")
+            .filter_map(|d| d.value)
+            .collect::<Vec<_>>()
+    } else {
+        let ast = RmlxParser::build_ast(&content).ok()?;
+        ast.directives
+            .into_iter()
+            .filter(|d| d.name == "use")
+            .filter_map(|d| d.value)
+            .collect::<Vec<_>>()
+    };
+
+    Some(
+        uses.iter()
+            .filter_map(|u| to_url(path, u).ok()?.to_file_path().ok())
+            .collect(),
+    )
+}
+
+fn collect_sources(path: &Path, sources: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if path.is_dir() {
+        for entry in std::fs::read_dir(path)? {
+            collect_sources(&entry?.path(), sources)?;
+        }
+    } else if is_source(path) {
+        sources.push(path.to_path_buf());
+    }
+
+    Ok(())
+}
+
+/// The `#use` relations between layouts and schemas.
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    dependencies: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl DependencyGraph {
+    /// Re-reads the directives of `path` and loads every schema it uses that isn't known yet.
+    /// If `path` can't be parsed, its previous dependencies are kept.
+    pub fn update(&mut self, path: &Path) {
+        let mut queue = VecDeque::from([path.to_path_buf()]);
+        while let Some(path) = queue.pop_front() {
+            let Some(dependencies) = read_dependencies(&path) else {
+                self.dependencies.entry(path).or_default();
+                continue;
+            };
+
+            queue.extend(
+                dependencies
+                    .iter()
+                    .filter(|d| !self.dependencies.contains_key(*d))
+                    .cloned(),
+            );
+            self.dependencies.insert(path, dependencies);
+        }
+    }
+
+    pub fn remove(&mut self, path: &Path) {
+        self.dependencies.remove(path);
+    }
+
+    #[must_use]
+    pub fn contains(&self, path: &Path) -> bool {
+        self.dependencies.contains_key(path)
+    }
+
+    #[must_use]
+    pub fn dependencies(&self, path: &Path) -> &[PathBuf] {
+        self.dependencies.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    /// All known files, sorted by path.
+    #[must_use]
+    pub fn files(&self) -> Vec<&Path> {
+        let mut files = self.dependencies.keys().map(PathBuf::as_path).collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    /// All known layouts, sorted by path.
+    #[must_use]
+    pub fn layouts(&self) -> Vec<PathBuf> {
+        self.files()
+            .into_iter()
+            .filter(|p| is_layout(p))
+            .map(Path::to_path_buf)
+            .collect()
+    }
+
+    /// Returns the known layouts that are among `changed` or use any of them, directly or through other schemas.
+    #[must_use]
+    pub fn affected_layouts(&self, changed: &[PathBuf]) -> Vec<PathBuf> {
+        let mut visited = changed.iter().map(PathBuf::as_path).collect::<HashSet<_>>();
+        let mut queue = visited.iter().copied().collect::<VecDeque<_>>();
+        while let Some(path) = queue.pop_front() {
+            for (dependent, dependencies) in &self.dependencies {
+                if dependencies.iter().any(|d| d == path) && visited.insert(dependent) {
+                    queue.push_back(dependent);
+                }
+            }
+        }
+
+        let mut layouts = visited
+            .into_iter()
+            .filter(|p| is_layout(p) && self.contains(p))
+            .map(Path::to_path_buf)
+            .collect::<Vec<_>>();
+        layouts.sort();
+        layouts
+    }
+}
+
+/// The outcome of validating a single layout.
+#[derive(Debug)]
+pub struct ValidationReport {
+    pub path: PathBuf,
+    pub result: Result<(), Error>,
+}
+
+pub fn validate_layout(path: &Path) -> Result<(), Error> {
+    let content = std::fs::read_to_string(path).map_err(|e| Error::LoadError(LoadError::LocalReadError(e)))?;
+    let ast = RmlParser::build_ast(&content)?;
+    LayoutModel::validate(ast, &path.to_string_lossy())?;
+    Ok(())
+}
+
+/// Watches files and directories of layouts and schemas and revalidates the layouts affected by each change.
+pub struct LayoutWatcher {
+    roots: Vec<PathBuf>,
+    graph: DependencyGraph,
+    watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    /// Directories of dependencies outside the roots.
+    watched: HashSet<PathBuf>,
+}
+
+impl LayoutWatcher {
+    pub fn new(paths: &[PathBuf]) -> notify::Result<Self> {
+        let (sender, events) = channel();
+        let mut this = Self {
+            roots: Vec::new(),
+            graph: DependencyGraph::default(),
+            watcher: notify::recommended_watcher(sender)?,
+            events,
+            watched: HashSet::default(),
+        };
+
+        for path in paths {
+            let path = path.canonicalize()?;
+            this.watcher.watch(&path, RecursiveMode::Recursive)?;
+
+            let mut sources = Vec::new();
+            collect_sources(&path, &mut sources)?;
+            sources.iter().for_each(|s| this.graph.update(s));
+            this.roots.push(path);
+        }
+        this.watch_dependencies();
+
+        Ok(this)
+    }
+
+    #[must_use]
+    pub fn graph(&self) -> &DependencyGraph {
+        &self.graph
+    }
+
+    /// Validates every known layout.
+    #[must_use]
+    pub fn validate_all(&self) -> Vec<ValidationReport> {
+        Self::validate(self.graph.layouts())
+    }
+
+    /// Blocks until a watched layout or schema changes and returns the reports of the affected layouts.
+    pub fn wait(&mut self) -> notify::Result<Vec<ValidationReport>> {
+        loop {
+            let changed = self.next_changes()?;
+            for path in &changed {
+                if path.exists() {
+                    self.graph.update(path);
+                } else {
+                    self.graph.remove(path);
+                }
+            }
+            self.watch_dependencies();
+
+            let affected = self.graph.affected_layouts(&changed);
+            if !affected.is_empty() {
+                return Ok(Self::validate(affected));
+            }
+        }
+    }
+
+    fn validate(layouts: Vec<PathBuf>) -> Vec<ValidationReport> {
+        layouts
+            .into_iter()
+            .map(|path| {
+                let result = validate_layout(&path);
+                ValidationReport { path, result }
+            })
+            .collect()
+    }
+
+    fn next_changes(&self) -> notify::Result<Vec<PathBuf>> {
+        let disconnected = || notify::Error::generic("File watcher disconnected");
+
+        let mut changed = BTreeSet::new();
+        let mut next = self.events.recv().map_err(|_| disconnected())?;
+        loop {
+            let event = next?;
+            if matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) {
+                changed.extend(event.paths.into_iter().filter(|p| self.is_tracked(p)));
+            }
+
+            match self.events.recv_timeout(DEBOUNCE) {
+                Ok(event) => next = event,
+                Err(_) if changed.is_empty() => next = self.events.recv().map_err(|_| disconnected())?,
+                Err(_) => break,
+            }
+        }
+
+        Ok(changed.into_iter().collect())
+    }
+
+    fn is_tracked(&self, path: &Path) -> bool {
+        is_source(path) && (self.graph.contains(path) || self.roots.iter().any(|r| path.starts_with(r)))
+    }
+
+    /// Schemas can be used from outside the watched roots, so their directories are watched as well.
+    fn watch_dependencies(&mut self) {
+        let directories = self
+            .graph
+            .files()
+            .into_iter()
+            .filter(|f| !self.roots.iter().any(|r| f.starts_with(r)))
+            .filter_map(Path::parent)
+            .map(Path::to_path_buf)
+            .collect::<BTreeSet<_>>();
+
+        for directory in directories {
+            if !self.watched.contains(&directory) && self.watcher.watch(&directory, RecursiveMode::NonRecursive).is_ok()
+            {
+                self.watched.insert(directory);
+            }
+        }
+    }
+}